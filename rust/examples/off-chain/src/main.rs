@@ -67,7 +67,7 @@ fn request_airdrop<C: Deref<Target = impl Signer> + Clone>(program: &Program<C>,
         .expect("Latest blockhash");
     let signature = program
         .rpc()
-        .request_airdrop_with_blockhash(&pubkey, 2 * LAMPORTS_PER_SOL, &latest_blockhash)
+        .request_airdrop_with_blockhash(pubkey, 2 * LAMPORTS_PER_SOL, &latest_blockhash)
         .expect("Airdrop tx");
     program
         .rpc()
@@ -86,7 +86,7 @@ fn get_program() -> (Pubkey, Program<Rc<Keypair>>) {
     let config_file = CONFIG_FILE
         .as_ref()
         .expect("unable to get config file path");
-    let cli_config = Config::load(&config_file).expect("Unable to load solana configuration");
+    let cli_config = Config::load(config_file).expect("Unable to load solana configuration");
     let payer =
         read_keypair_file(&cli_config.keypair_path).expect("Example requires a keypair file");
     let payer_pubkey = payer.pubkey();
@@ -107,7 +107,7 @@ pub fn wait_fulfilled<C: Deref<Target = impl Signer> + Clone>(
     let progress = ProgressBar::new_spinner();
     progress.enable_steady_tick(std::time::Duration::from_millis(120));
     progress.set_message("Waiting for randomness being fulfilled..");
-    let randomness_address = orao_solana_vrf::randomness_account_address(&seed);
+    let randomness_address = orao_solana_vrf::randomness_account_address(seed);
     loop {
         match program.account::<Randomness>(randomness_address) {
             Ok(randomness) if randomness.fulfilled().is_some() => break randomness,
@@ -0,0 +1,51 @@
+use anchor_client::solana_sdk::bs58;
+use anchor_lang::prelude::Pubkey;
+
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: offline-verify <SEED> <AUTHORITY> <RESPONSE_SIGNATURE>
+
+Verifies the response of a single fulfillment authority to the randomness request.
+
+  <SEED>                base58-encoded 32-byte seed of the request
+  <AUTHORITY>           base58-encoded public key of the fulfillment authority
+  <RESPONSE_SIGNATURE>  base58-encoded 64-byte response of the AUTHORITY, i.e. its
+                        Ed25519 signature of the SEED (see `RandomnessResponse`);
+                        not the fulfilled randomness, which combines the responses
+                        of all the authorities";
+
+pub fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [seed, authority, response_signature] = args.as_slice() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    let seed: [u8; 32] = decode(seed, "Seed");
+    let authority = Pubkey::new_from_array(decode(authority, "Authority"));
+    let signature: [u8; 64] = decode(response_signature, "Response signature");
+
+    // This is all the verification needs – no RPC involved.
+    let result = orao_solana_vrf::verify_signature(&seed, &signature, &authority).is_ok();
+
+    println!("Verified: {}", result);
+
+    if result {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Decodes a base58-encoded array, exits the process on failure.
+fn decode<const N: usize>(value: &str, name: &str) -> [u8; N] {
+    let bytes = bs58::decode(value).into_vec().unwrap_or_else(|err| {
+        eprintln!("{} is not a valid base58: {}", name, err);
+        std::process::exit(2);
+    });
+    bytes.try_into().unwrap_or_else(|bytes: Vec<u8>| {
+        eprintln!("{} must be {} bytes long, got {}", name, N, bytes.len());
+        std::process::exit(2);
+    })
+}
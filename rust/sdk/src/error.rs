@@ -32,3 +32,175 @@ pub enum Error {
     #[msg("UnknownTreasuryGiven")]
     UnknownTreasuryGiven,
 }
+
+/// Errors reported by the SDK helpers.
+///
+/// These are client-side errors never returned by the on-chain program.
+/// SDK helpers report them as [`anchor_client::ClientError::AnchorError`],
+/// use [`SdkError::matches`] to tell them apart:
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use orao_solana_vrf::SdkError;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// match orao_solana_vrf::get_randomness(&program, &seed) {
+///     Ok(randomness) => println!("Randomness status: {:?}", randomness.status()),
+///     Err(err) if SdkError::UnexpectedAccountSize.matches(&err) => {
+///         println!("VRF account layout has changed")
+///     }
+///     Err(err) => return Err(err.into()),
+/// }
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "sdk")]
+#[non_exhaustive]
+#[error_code(offset = 7000)]
+pub enum SdkError {
+    #[msg("Seed prefix must be 1 to 32 bytes long")]
+    InvalidSeedPrefix,
+
+    #[msg("Account data size doesn't match the expected account layout")]
+    UnexpectedAccountSize,
+
+    #[msg("Randomness is not signed by the expected fulfillment authority")]
+    UnexpectedAuthority,
+
+    #[msg("Account is not owned by the VRF program")]
+    UnexpectedAccountOwner,
+
+    #[msg("VRF program is not deployed at the given address")]
+    ProgramNotDeployed,
+
+    #[msg("Not enough fulfillment authorities have responded")]
+    InsufficientResponders,
+
+    #[msg("Randomness account seed doesn't match the requested seed")]
+    SeedMismatch,
+
+    #[msg("Timed out waiting for the randomness request")]
+    Timeout,
+
+    #[msg("Request transaction failed on-chain")]
+    RequestTransactionFailed,
+
+    #[msg("Randomness is not yet fulfilled")]
+    RandomnessNotFulfilled,
+
+    #[msg("Request transaction simulation failed")]
+    SimulationFailed,
+}
+
+#[cfg(feature = "sdk")]
+impl SdkError {
+    /// Checks whether the client error is this error.
+    pub fn matches(self, err: &anchor_client::ClientError) -> bool {
+        let expected = anchor_lang::error::Error::from(self);
+        matches!(err, anchor_client::ClientError::AnchorError(err) if *err == expected)
+    }
+}
+
+#[cfg(feature = "sdk")]
+impl Error {
+    /// Checks whether the client error is this error.
+    ///
+    /// Matches both the errors reported by the SDK helpers and the errors
+    /// of transactions that failed on-chain.
+    pub fn matches(self, err: &anchor_client::ClientError) -> bool {
+        use anchor_client::solana_sdk::{
+            instruction::InstructionError, transaction::TransactionError,
+        };
+
+        match err {
+            anchor_client::ClientError::AnchorError(err) => {
+                *err == anchor_lang::error::Error::from(self)
+            }
+            anchor_client::ClientError::SolanaClientError(err) => matches!(
+                err.get_transaction_error(),
+                Some(TransactionError::InstructionError(_, InstructionError::Custom(code)))
+                    if code == u32::from(self)
+            ),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "sdk")]
+impl From<Error> for anchor_client::ClientError {
+    fn from(err: Error) -> Self {
+        anchor_lang::error::Error::from(err).into()
+    }
+}
+
+#[cfg(feature = "sdk")]
+impl From<SdkError> for anchor_client::ClientError {
+    fn from(err: SdkError) -> Self {
+        anchor_lang::error::Error::from(err).into()
+    }
+}
+
+/// Error of decoding a VRF account (see [`crate::state::Randomness::decode_from_bytes`]).
+///
+/// Converts into [`anchor_client::ClientError`], so that SDK helpers report it along with
+/// other errors: [`AccountDecodeError::AccountLayoutMismatch`] is reported as
+/// [`SdkError::UnexpectedAccountSize`] carrying the expected and the actual sizes.
+#[cfg(feature = "sdk")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountDecodeError {
+    /// Account data size doesn't match the expected account layout,
+    /// e.g. after a program upgrade changed the layout.
+    AccountLayoutMismatch {
+        /// Expected size in bytes.
+        expected: usize,
+        /// Actual size in bytes.
+        found: usize,
+    },
+    /// Account data doesn't start with the expected discriminator.
+    DiscriminatorMismatch,
+    /// Account data doesn't deserialize.
+    DidNotDeserialize,
+}
+
+#[cfg(feature = "sdk")]
+impl std::fmt::Display for AccountDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountDecodeError::AccountLayoutMismatch { expected, found } => write!(
+                f,
+                "Account layout mismatch: expected {} bytes, found {}",
+                expected, found
+            ),
+            AccountDecodeError::DiscriminatorMismatch => {
+                f.write_str("Account discriminator mismatch")
+            }
+            AccountDecodeError::DidNotDeserialize => f.write_str("Account did not deserialize"),
+        }
+    }
+}
+
+#[cfg(feature = "sdk")]
+impl std::error::Error for AccountDecodeError {}
+
+#[cfg(feature = "sdk")]
+impl From<AccountDecodeError> for anchor_client::ClientError {
+    fn from(err: AccountDecodeError) -> Self {
+        match err {
+            AccountDecodeError::AccountLayoutMismatch { expected, found } => {
+                anchor_lang::error::Error::from(SdkError::UnexpectedAccountSize)
+                    .with_values((expected, found))
+                    .into()
+            }
+            AccountDecodeError::DiscriminatorMismatch => {
+                anchor_lang::error::Error::from(ErrorCode::AccountDiscriminatorMismatch).into()
+            }
+            AccountDecodeError::DidNotDeserialize => {
+                anchor_lang::error::Error::from(ErrorCode::AccountDidNotDeserialize).into()
+            }
+        }
+    }
+}
@@ -10,11 +10,38 @@
 //! Please look into the following functions and structures:
 //!
 //! * [`RequestBuilder`] – convenient builder for randomness requests
+//! * [`BatchRequestBuilder`] – builder for batched randomness requests
+//! * [`random_seed`] – helper to generate a unique request seed
 //! * [`get_network_state`] – helper to fetch the VRF configuration
 //! * [`get_randomness`] – helper to fetch the randomness request state
+//! * [`get_or_request`] – canonical entry point to get fulfilled randomness for a seed
+//! * [`FulfillmentWaiter`] – helper to wait for the randomness being fulfilled
+//! * [`verify_fulfillment`] – helper to verify randomness against its fulfill transactions
 //! * [`randomness_account_address`] – helper to derive randomness request state address
 //! * [`network_state_account_address`] – helper to derive VRF on-chain configuration address
 //!
+//! Async counterparts based on the nonblocking RPC client live in the `nonblocking` module
+//! (requires `async` feature to be enabled). The `replay` module records and replays
+//! their RPC interactions for deterministic offline tests.
+//!
+//! ## Verification
+//!
+//! Every fulfillment authority response is an Ed25519 signature of the seed, and
+//! the randomness is the XOR of the responses, so verification boils down to checking
+//! these signatures – see [`state::Randomness::verify_offchain`] (against the effective
+//! fulfillment authorities) and [`verify_fulfillment`] (against the fulfill transactions).
+//!
+//! The program doesn't commit fulfillments into any accumulator (e.g. a Merkle tree),
+//! so there are no inclusion proofs to verify – the signatures are the only proof.
+//!
+//! ## Networks
+//!
+//! The VRF is deployed at the same program id ([`id`]) on every network, and its accounts
+//! are derived from the program id, so the same helpers work on Mainnet, Devnet or
+//! a private RPC provider – pick the network with [`anchor_client::Cluster`],
+//! e.g. `Cluster::Mainnet` or `Cluster::Custom(rpc_url, ws_url)`. Use [`Env`]
+//! for a deployment at a different program id.
+//!
 //! ## Cross Program Invocation
 //!
 //! For CPI please look into the `cpi` example and accouns requiremens for the [`Request`]
@@ -40,10 +67,14 @@ use anchor_lang::prelude::*;
 use state::{NetworkState, OraoTokenFeeConfig, Randomness};
 
 pub use crate::error::Error;
+#[cfg(feature = "sdk")]
+pub use crate::error::{AccountDecodeError, SdkError};
 
 pub mod error;
 pub mod state;
 
+// SDK helpers report `anchor_client::ClientError` as is
+#[allow(clippy::result_large_err)]
 mod sdk;
 #[cfg(feature = "sdk")]
 pub use crate::sdk::*;
@@ -60,14 +91,87 @@ pub const CONFIG_ACCOUNT_SEED: &[u8] = b"orao-vrf-network-configuration";
 // this is the number of public keys that are allowed to generate randomness
 pub const MAX_FULFILLMENT_AUTHORITIES_COUNT: usize = 10;
 
+/// Size of the randomness request account (including the discriminator).
+pub const RANDOMNESS_ACCOUNT_SIZE: usize = 8 + 32 + 64 + 4 + (32 + 64) * 7;
+
 /// Returns network state account address.
 pub fn network_state_account_address() -> Pubkey {
-    Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED.as_ref()], &crate::id()).0
+    network_state_account_address_for(&crate::id())
+}
+
+/// Returns network state account address of the VRF deployed at `program_id`.
+pub fn network_state_account_address_for(program_id: &Pubkey) -> Pubkey {
+    network_state_account_address_with_bump_for(program_id).0
+}
+
+/// Returns network state account address along with its bump
+/// for the VRF deployed at `program_id`.
+///
+/// The address never changes, so with `sdk` feature enabled it is derived once
+/// for the default program id and cached afterwards.
+pub fn network_state_account_address_with_bump_for(program_id: &Pubkey) -> (Pubkey, u8) {
+    let derive = || Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], program_id);
+
+    #[cfg(feature = "sdk")]
+    if *program_id == crate::id() {
+        static CACHED: std::sync::OnceLock<(Pubkey, u8)> = std::sync::OnceLock::new();
+        return *CACHED.get_or_init(derive);
+    }
+
+    derive()
 }
 
 /// Returns randomness account address for the given seed.
 pub fn randomness_account_address(seed: &[u8; 32]) -> Pubkey {
-    Pubkey::find_program_address(&[RANDOMNESS_ACCOUNT_SEED.as_ref(), &seed[..]], &crate::id()).0
+    randomness_account_address_for(&crate::id(), seed)
+}
+
+/// Returns randomness account address for the given seed of the VRF deployed at `program_id`.
+pub fn randomness_account_address_for(program_id: &Pubkey, seed: &[u8; 32]) -> Pubkey {
+    randomness_account_address_with_bump_for(program_id, seed).0
+}
+
+/// Returns randomness account address along with its bump for the given seed
+/// of the VRF deployed at `program_id`.
+///
+/// Store the bump to derive the address cheaply afterwards
+/// (see [`create_randomness_account_address_for`]).
+pub fn randomness_account_address_with_bump_for(
+    program_id: &Pubkey,
+    seed: &[u8; 32],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RANDOMNESS_ACCOUNT_SEED, &seed[..]], program_id)
+}
+
+/// Returns randomness account address for the given seed and bump
+/// of the VRF deployed at `program_id`.
+///
+/// Skips the bump search of [`randomness_account_address_for`], so it is
+/// the right choice for hot paths. The result is only the canonical address
+/// if the bump was obtained from [`randomness_account_address_with_bump_for`].
+pub fn create_randomness_account_address_for(
+    program_id: &Pubkey,
+    seed: &[u8; 32],
+    bump: u8,
+) -> std::result::Result<Pubkey, anchor_lang::solana_program::pubkey::PubkeyError> {
+    Pubkey::create_program_address(&[RANDOMNESS_ACCOUNT_SEED, &seed[..], &[bump]], program_id)
+}
+
+/// Returns a request seed namespaced under the PDA of the integrator program.
+///
+/// The derivation has two levels:
+///
+/// 1. the PDA of `program_id` for the given `seeds` is derived
+///    (as with [`Pubkey::find_program_address`]),
+/// 2. the PDA bytes are used as the request seed, so the randomness account address
+///    is derived from it as usual (see [`randomness_account_address`]).
+///
+/// Seeds of different programs never collide, so this gives program-scoped seeds
+/// for multi-tenant deployments. Note that a seed is single-use, so `seeds` should
+/// include something unique per request (e.g. a counter). Returns `None` if the
+/// `seeds` are invalid (see [`Pubkey::try_find_program_address`]).
+pub fn namespaced_seed(program_id: &Pubkey, seeds: &[&[u8]]) -> Option<[u8; 32]> {
+    Pubkey::try_find_program_address(seeds, program_id).map(|(address, _)| address.to_bytes())
 }
 
 /// Helper that XORes `r` into `l`.
@@ -163,7 +267,7 @@ pub struct InitNetwork<'info> {
         init,
         payer = payer,
         space = 8 + 464,
-        seeds = [CONFIG_ACCOUNT_SEED.as_ref()],
+        seeds = [CONFIG_ACCOUNT_SEED],
         bump,
     )]
     network_state: Account<'info, NetworkState>,
@@ -193,7 +297,7 @@ pub struct UpdateNetwork<'info> {
     authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [CONFIG_ACCOUNT_SEED.as_ref()],
+        seeds = [CONFIG_ACCOUNT_SEED],
         bump,
         constraint = network_state.config.authority == authority.key(),
     )]
@@ -229,7 +333,7 @@ pub struct Request<'info> {
     payer: Signer<'info>,
     #[account(
         mut,
-        seeds = [CONFIG_ACCOUNT_SEED.as_ref()],
+        seeds = [CONFIG_ACCOUNT_SEED],
         bump,
     )]
     network_state: Account<'info, NetworkState>,
@@ -243,8 +347,8 @@ pub struct Request<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 64 + 4 + (32 + 64) * 7,
-        seeds = [RANDOMNESS_ACCOUNT_SEED.as_ref(), &seed],
+        space = RANDOMNESS_ACCOUNT_SIZE,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &seed],
         bump,
     )]
     request: Account<'info, Randomness>,
@@ -269,13 +373,13 @@ pub struct Fulfill<'info> {
     instruction_acc: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [CONFIG_ACCOUNT_SEED.as_ref()],
+        seeds = [CONFIG_ACCOUNT_SEED],
         bump,
     )]
     network_state: Account<'info, NetworkState>,
     #[account(
         mut,
-        seeds = [RANDOMNESS_ACCOUNT_SEED.as_ref(), &request.seed],
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &request.seed],
         bump,
     )]
     request: Account<'info, Randomness>,
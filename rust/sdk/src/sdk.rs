@@ -1,27 +1,70 @@
 #![cfg(feature = "sdk")]
 
 use anchor_client::{
-    solana_client::client_error::{ClientError, ClientErrorKind},
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_config::RpcAccountInfoConfig,
+        rpc_request::MAX_MULTIPLE_ACCOUNTS,
+    },
     solana_sdk::{
-        ed25519_instruction,
-        signature::{Keypair, Signature},
-        signer::Signer,
-        sysvar,
+        account::Account, compute_budget::ComputeBudgetInstruction, ed25519_instruction,
+        instruction::Instruction, signature::Keypair, signer::Signer, sysvar,
     },
 };
 use anchor_lang::{
     prelude::{AccountMeta, Pubkey},
-    system_program,
+    solana_program::pubkey::MAX_SEED_LEN,
+    system_program, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas,
 };
 use anchor_spl::token;
+use solana_account_decoder::UiAccountEncoding;
 
 use crate::{
-    network_state_account_address, quorum, randomness_account_address,
-    state::{NetworkConfiguration, NetworkState, OraoTokenFeeConfig, Randomness},
-    xor_array,
+    network_state_account_address_for, quorum, randomness_account_address_for,
+    state::{NetworkConfiguration, NetworkState, OraoTokenFeeConfig, Randomness, RandomnessStatus},
+    xor_array, AccountDecodeError, SdkError, RANDOMNESS_ACCOUNT_SIZE,
 };
 
-use std::ops::Deref;
+use std::{ops::Deref, time::Duration};
+
+mod audit;
+mod batch;
+mod confirm;
+mod env;
+mod history;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+mod priority;
+#[cfg(feature = "async")]
+pub mod replay;
+mod request;
+mod retry;
+mod seed;
+mod snapshot;
+mod subscribe;
+mod verifier;
+mod verify;
+mod wait;
+pub use audit::*;
+pub use batch::*;
+pub use confirm::*;
+pub use env::*;
+pub use history::*;
+pub use priority::*;
+pub use request::*;
+pub use retry::*;
+pub use seed::*;
+pub use snapshot::*;
+pub use subscribe::*;
+pub use verifier::*;
+pub use verify::*;
+pub use wait::*;
+
+/// How many times [`get_randomness`] re-fetches an account that has no data yet.
+const EMPTY_DATA_RETRIES: u32 = 3;
+
+/// Delay between re-fetches of an account that has no data yet.
+const EMPTY_DATA_RETRY_DELAY: Duration = Duration::from_millis(200);
 
 /// Fetches VRF on-chain state.
 ///
@@ -41,12 +84,59 @@ use std::ops::Deref;
 pub fn get_network_state<C: Deref<Target = impl Signer> + Clone>(
     orao_vrf: &anchor_client::Program<C>,
 ) -> Result<NetworkState, anchor_client::ClientError> {
-    let network_state_address = network_state_account_address();
+    let network_state_address = network_state_account_address_for(&orao_vrf.id());
     orao_vrf.account(network_state_address)
 }
 
+/// Fetches the VRF on-chain state of the deployment described by the `env`
+/// (see [`get_network_state`] and [`Env`]).
+pub fn get_network_state_with_env<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    env: &Env,
+) -> Result<NetworkState, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let account = rpc
+        .get_account_with_commitment(&env.network_state_account_address(), rpc.commitment())?
+        .value
+        .ok_or(anchor_client::ClientError::AccountNotFound)?;
+    env.decode_network_state(&account)
+}
+
+/// Oracle parameters needed to estimate the request cost (see [`get_oracle_params`]).
+#[derive(Debug, Clone)]
+pub struct OracleParams {
+    /// VRF configuration authority.
+    pub authority: Pubkey,
+    /// Treasury receiving the request fees paid in lamports.
+    pub treasury: Pubkey,
+    /// Request fee in lamports.
+    pub fee: u64,
+    /// Token fee configuration, if fees could be paid in SPL token.
+    pub token_fee: Option<OraoTokenFeeConfig>,
+}
+
+/// Fetches the oracle parameters with a single VRF configuration read.
+pub fn get_oracle_params<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+) -> Result<OracleParams, anchor_client::ClientError> {
+    let config = get_network_state(orao_vrf)?.config;
+    Ok(OracleParams {
+        authority: config.authority,
+        treasury: config.treasury,
+        fee: config.request_fee,
+        token_fee: config.token_fee_config,
+    })
+}
+
 /// Fetches randomness request state for the given seed.
 ///
+/// A request that is not yet fulfilled is returned as is, use [`Randomness::status`]
+/// to tell it apart. Fails with [`anchor_client::ClientError::AccountNotFound`]
+/// if the seed was never requested.
+///
+/// An account without data (as returned by a lagging RPC node right after the request)
+/// is re-fetched a few times with a short delay before failing to decode.
+///
 /// ```no_run
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// use anchor_client::*;
@@ -69,8 +159,286 @@ pub fn get_randomness<C: Deref<Target = impl Signer> + Clone>(
     orao_vrf: &anchor_client::Program<C>,
     seed: &[u8; 32],
 ) -> Result<Randomness, anchor_client::ClientError> {
-    let request_address = randomness_account_address(seed);
-    orao_vrf.account(request_address)
+    get_randomness_with_env(orao_vrf, &Env::new(orao_vrf.id()), seed)
+}
+
+/// Fetches randomness request state for the given seed from the deployment described
+/// by the `env` (see [`get_randomness`] and [`Env`]).
+pub fn get_randomness_with_env<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    env: &Env,
+    seed: &[u8; 32],
+) -> Result<Randomness, anchor_client::ClientError> {
+    let request_address = env.randomness_account_address(seed);
+    let rpc = orao_vrf.rpc();
+    let mut retries = 0;
+    loop {
+        let account = rpc
+            .get_account_with_commitment(&request_address, rpc.commitment())?
+            .value
+            .ok_or(anchor_client::ClientError::AccountNotFound)?;
+        // a lagging RPC node might return a just created account without the data
+        if account.data.is_empty() && retries < EMPTY_DATA_RETRIES {
+            std::thread::sleep(EMPTY_DATA_RETRY_DELAY);
+            retries += 1;
+            continue;
+        }
+        return env.decode_randomness(&account);
+    }
+}
+
+/// Fetches randomness request status for the given seed.
+///
+/// Unlike [`get_randomness`], a seed that was never requested is not an error but
+/// [`RandomnessStatus::NotRequested`] – only actual failures (e.g. RPC errors
+/// or an invalid account) are returned as errors.
+pub fn get_randomness_status<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+) -> Result<RandomnessStatus, anchor_client::ClientError> {
+    match get_randomness(orao_vrf, seed) {
+        Ok(randomness) => Ok(randomness.status()),
+        Err(anchor_client::ClientError::AccountNotFound) => Ok(RandomnessStatus::NotRequested),
+        Err(err) => Err(err),
+    }
+}
+
+/// Fetches randomness request state for many seeds (see [`get_randomness`]).
+///
+/// Accounts are fetched with a single `getMultipleAccounts` call per
+/// [`MAX_MULTIPLE_ACCOUNTS`] seeds. Returns the outcome for every seed in the given order –
+/// a missing account or an account that fails to decode doesn't fail the whole batch
+/// (missing accounts are reported as [`anchor_client::ClientError::AccountNotFound`]).
+pub fn get_randomness_batch<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seeds: &[[u8; 32]],
+) -> Result<Vec<Result<Randomness, anchor_client::ClientError>>, anchor_client::ClientError> {
+    get_randomness_batch_with_env(orao_vrf, &Env::new(orao_vrf.id()), seeds)
+}
+
+/// Fetches randomness request state for many seeds from the deployment described
+/// by the `env` (see [`get_randomness_batch`] and [`Env`]).
+pub fn get_randomness_batch_with_env<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    env: &Env,
+    seeds: &[[u8; 32]],
+) -> Result<Vec<Result<Randomness, anchor_client::ClientError>>, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let mut batch = Vec::with_capacity(seeds.len());
+    for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses = chunk
+            .iter()
+            .map(|seed| env.randomness_account_address(seed))
+            .collect::<Vec<_>>();
+        let accounts = rpc
+            .get_multiple_accounts_with_commitment(&addresses, rpc.commitment())?
+            .value;
+        batch.extend(accounts.into_iter().map(|account| {
+            let account = account.ok_or(anchor_client::ClientError::AccountNotFound)?;
+            env.decode_randomness(&account)
+        }));
+    }
+    Ok(batch)
+}
+
+/// Fetches randomness request state for the given seed using the given account config
+/// (see [`get_randomness`]).
+///
+/// Use [`RpcAccountInfoConfig::min_context_slot`] to avoid stale data of a lagging RPC node,
+/// e.g. when polling across many RPC nodes – the RPC fails with an error
+/// instead of returning data older than that slot. The commitment defaults
+/// to the client commitment, and the encoding is always base64.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::{solana_client::rpc_config::RpcAccountInfoConfig, *};
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// # let fulfilled_at: u64 = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let config = RpcAccountInfoConfig {
+///     min_context_slot: Some(fulfilled_at),
+///     ..Default::default()
+/// };
+/// let randomness = orao_solana_vrf::get_randomness_with_config(&program, &seed, config)?;
+/// # Ok(()) }
+/// ```
+pub fn get_randomness_with_config<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    config: RpcAccountInfoConfig,
+) -> Result<Randomness, anchor_client::ClientError> {
+    let request_address = randomness_account_address_for(&orao_vrf.id(), seed);
+    let rpc = orao_vrf.rpc();
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: config.commitment.or(Some(rpc.commitment())),
+        ..config
+    };
+    let account = rpc
+        .get_account_with_config(&request_address, config)?
+        .value
+        .ok_or(anchor_client::ClientError::AccountNotFound)?;
+    Randomness::decode_from_account(&orao_vrf.id(), &account)
+}
+
+/// Returns the fulfillment authorities that have responded to the request for the given seed.
+///
+/// Responders are stored in the randomness account, so this takes a single account fetch
+/// rather than a transaction scan. Cache them to verify later responses with
+/// [`Randomness::verify_response`].
+pub fn get_fulfillment_authorities<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+) -> Result<Vec<Pubkey>, anchor_client::ClientError> {
+    let randomness = get_randomness(orao_vrf, seed)?;
+    randomness.check_seed(seed)?;
+    Ok(randomness
+        .responses
+        .iter()
+        .map(|response| response.pubkey)
+        .collect())
+}
+
+/// Complete on-chain picture of a randomness request (see [`get_randomness_full`]).
+#[derive(Debug, Clone)]
+pub struct RandomnessAccount {
+    /// Randomness account address.
+    pub address: Pubkey,
+    /// Slot at which the account state was observed.
+    pub slot: u64,
+    /// Account balance in lamports.
+    pub lamports: u64,
+    /// Decoded randomness request state.
+    pub randomness: Randomness,
+    /// Raw account data.
+    pub data: Vec<u8>,
+}
+
+impl RandomnessAccount {
+    /// Returns the request status (see [`Randomness::status`]).
+    pub fn status(&self) -> RandomnessStatus {
+        self.randomness.status()
+    }
+
+    /// Returns the request seed.
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.randomness.seed
+    }
+
+    /// Returns the fulfillment authorities that have responded so far.
+    pub fn responders(&self) -> impl Iterator<Item = &Pubkey> {
+        self.randomness.responses.iter().map(|x| &x.pubkey)
+    }
+}
+
+/// Fetches randomness request state for the given seed along with the account details
+/// (see [`get_randomness`]).
+pub fn get_randomness_full<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+) -> Result<RandomnessAccount, anchor_client::ClientError> {
+    get_randomness_full_with_env(orao_vrf, &Env::new(orao_vrf.id()), seed)
+}
+
+/// Fetches randomness request state for the given seed along with the account details
+/// from the deployment described by the `env` (see [`get_randomness_full`] and [`Env`]).
+pub fn get_randomness_full_with_env<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    env: &Env,
+    seed: &[u8; 32],
+) -> Result<RandomnessAccount, anchor_client::ClientError> {
+    let address = env.randomness_account_address(seed);
+    let rpc = orao_vrf.rpc();
+    let response = rpc.get_account_with_commitment(&address, rpc.commitment())?;
+    let account = response
+        .value
+        .ok_or(anchor_client::ClientError::AccountNotFound)?;
+    Ok(RandomnessAccount {
+        address,
+        slot: response.context.slot,
+        lamports: account.lamports,
+        randomness: env.decode_randomness(&account)?,
+        data: account.data,
+    })
+}
+
+/// Looks for the randomness of the given seed under each of the candidate program ids.
+///
+/// Randomness account addresses depend on the program id, so randomness requested under
+/// an old program id is not found under a new one. Use this to locate randomness across
+/// program migrations. Returns the program ids that have the randomness along with it
+/// (in the given order).
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// # let old_id: solana_sdk::pubkey::Pubkey = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// for (program_id, randomness) in
+///     orao_solana_vrf::locate_randomness(&program, &seed, &[program.id(), old_id])?
+/// {
+///     println!("Found under {}: {:?}", program_id, randomness.status());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn locate_randomness<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    program_ids: &[Pubkey],
+) -> Result<Vec<(Pubkey, Randomness)>, anchor_client::ClientError> {
+    let mut found = Vec::new();
+    for chunk in program_ids.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses = chunk
+            .iter()
+            .map(|program_id| randomness_account_address_for(program_id, seed))
+            .collect::<Vec<_>>();
+        let accounts = orao_vrf.rpc().get_multiple_accounts(&addresses)?;
+        for (program_id, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                let randomness = Randomness::decode_from_account(program_id, &account)?;
+                found.push((*program_id, randomness));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Validates a custom PDA seed prefix.
+///
+/// The prefix is combined with the 32-byte request seed to derive account addresses,
+/// so it is subject to the same limits (see [`MAX_SEED_LEN`]). Fails with
+/// [`SdkError::InvalidSeedPrefix`] instead of panicking inside `find_program_address`.
+pub fn validate_seed_prefix(prefix: &[u8]) -> Result<(), anchor_client::ClientError> {
+    if prefix.is_empty() || prefix.len() > MAX_SEED_LEN {
+        return Err(SdkError::InvalidSeedPrefix.into());
+    }
+    Ok(())
+}
+
+/// Checks that the VRF program is deployed, i.e. the program account exists and is executable.
+///
+/// Fails with [`SdkError::ProgramNotDeployed`] otherwise. Call it once at startup
+/// to catch a misconfigured program id or cluster early.
+pub fn verify_program_deployed<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+) -> Result<(), anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let account = rpc
+        .get_account_with_commitment(&orao_vrf.id(), rpc.commitment())?
+        .value;
+    match account {
+        Some(account) if account.executable => Ok(()),
+        _ => Err(SdkError::ProgramNotDeployed.into()),
+    }
 }
 
 /// `init_network` instruction builder.
@@ -108,8 +476,8 @@ impl InitBuilder {
     pub fn build<C: Deref<Target = impl Signer> + Clone>(
         self,
         orao_vrf: &anchor_client::Program<C>,
-    ) -> Result<anchor_client::RequestBuilder<C>, anchor_client::ClientError> {
-        let network_state_address = network_state_account_address();
+    ) -> Result<anchor_client::RequestBuilder<'_, C>, anchor_client::ClientError> {
+        let network_state_address = network_state_account_address_for(&orao_vrf.id());
 
         let builder = orao_vrf
             .request()
@@ -187,8 +555,8 @@ impl UpdateBuilder {
     pub fn build<C: Deref<Target = impl Signer> + Clone>(
         self,
         orao_vrf: &anchor_client::Program<C>,
-    ) -> Result<anchor_client::RequestBuilder<C>, anchor_client::ClientError> {
-        let network_state_address = network_state_account_address();
+    ) -> Result<anchor_client::RequestBuilder<'_, C>, anchor_client::ClientError> {
+        let network_state_address = network_state_account_address_for(&orao_vrf.id());
         let network_state: NetworkState = orao_vrf.account(network_state_address)?;
         let mut config = network_state.config;
 
@@ -235,6 +603,9 @@ impl UpdateBuilder {
 
 /// `request` instruction builder.
 ///
+/// Accounts are derived for the VRF deployed at the given program id
+/// (see [`anchor_client::Program::id`]).
+///
 /// ```no_run
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// use anchor_client::*;
@@ -243,7 +614,7 @@ impl UpdateBuilder {
 /// let client = Client::new(Cluster::Devnet, payer);
 /// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
 ///
-/// let seed = rand::random();
+/// let seed = orao_solana_vrf::random_seed();
 /// let tx = orao_solana_vrf::RequestBuilder::new(seed)
 ///     .build(&program)?
 ///     .send()?;
@@ -251,10 +622,12 @@ impl UpdateBuilder {
 /// println!("Your transaction is {}", tx);
 /// # Ok(()) }
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RequestBuilder {
     seed: [u8; 32],
     token_wallet: Option<Pubkey>,
+    compute_unit_price: Option<u64>,
+    compute_unit_limit: Option<u32>,
 }
 
 impl RequestBuilder {
@@ -263,9 +636,36 @@ impl RequestBuilder {
         Self {
             seed,
             token_wallet: None,
+            compute_unit_price: None,
+            compute_unit_limit: None,
         }
     }
 
+    /// Creates a new builder that uses the latest blockhash as the seed.
+    ///
+    /// The seed is tied to the recent chain state, but note that seeds are unique,
+    /// so only one request is possible per blockhash. Use [`RequestBuilder::seed`]
+    /// to get the seed used.
+    pub fn from_latest_blockhash<C: Deref<Target = impl Signer> + Clone>(
+        orao_vrf: &anchor_client::Program<C>,
+    ) -> Result<Self, anchor_client::ClientError> {
+        let blockhash = orao_vrf.rpc().get_latest_blockhash()?;
+        Ok(Self::new(blockhash.to_bytes()))
+    }
+
+    /// Creates a new builder that uses a seed derived from the most recent slot hash
+    /// (see [`recent_slot_hash_seed`]).
+    pub fn from_recent_slot_hash<C: Deref<Target = impl Signer> + Clone>(
+        orao_vrf: &anchor_client::Program<C>,
+    ) -> Result<Self, anchor_client::ClientError> {
+        Ok(Self::new(recent_slot_hash_seed(orao_vrf)?.seed))
+    }
+
+    /// Returns the request seed.
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.seed
+    }
+
     /// Pay fees with SPL token using given token wallet address.
     ///
     /// Instruction could fail if token fee is not configured for the contract.
@@ -274,45 +674,148 @@ impl RequestBuilder {
         self
     }
 
+    /// Pay priority fee of the given level (see [`FeeLevel`]).
+    pub fn with_fee_level(mut self, fee_level: FeeLevel) -> Self {
+        self.compute_unit_price = fee_level.compute_unit_price();
+        self
+    }
+
+    /// Pay priority fee of the given compute unit price (in micro-lamports).
+    pub fn with_compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Request the given compute unit limit for the transaction.
+    ///
+    /// The priority fee is paid per requested compute unit, so a tight limit
+    /// lowers the fee.
+    pub fn with_compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Pay priority fee at the given percentile (e.g. `75`) of the recent fees paid
+    /// for the accounts of the request (see [`recent_fee_percentile`]).
+    ///
+    /// The fee adapts to the current congestion, but it is computed once –
+    /// call this right before sending the request.
+    pub fn with_recent_fee_percentile<C: Deref<Target = impl Signer> + Clone>(
+        mut self,
+        orao_vrf: &anchor_client::Program<C>,
+        percentile: u8,
+    ) -> Result<Self, anchor_client::ClientError> {
+        let accounts = [
+            orao_vrf.payer(),
+            network_state_account_address_for(&orao_vrf.id()),
+            randomness_account_address_for(&orao_vrf.id(), &self.seed),
+        ];
+        let compute_unit_price = recent_fee_percentile(orao_vrf, &accounts, percentile)?;
+        self.compute_unit_price = (compute_unit_price > 0).then_some(compute_unit_price);
+        Ok(self)
+    }
+
     /// Builds the request.
     pub fn build<C: Deref<Target = impl Signer> + Clone>(
         self,
         orao_vrf: &anchor_client::Program<C>,
-    ) -> Result<anchor_client::RequestBuilder<C>, anchor_client::ClientError> {
-        let network_state_address = network_state_account_address();
-        let request_address = randomness_account_address(&self.seed);
+    ) -> Result<anchor_client::RequestBuilder<'_, C>, anchor_client::ClientError> {
+        self.build_with_env(orao_vrf, &Env::new(orao_vrf.id()))
+    }
 
-        let network_state: NetworkState = orao_vrf.account(network_state_address)?;
-        let config = network_state.config;
-
-        let (treasury, remaining_accounts) = if let Some(token_wallet) = self.token_wallet {
-            let token_fee_config = config.token_fee_config.ok_or_else(|| {
-                ClientError::from(ClientErrorKind::Custom(format!(
-                    "Token fee is not configured for the given VRF instance"
-                )))
-            })?;
-            (
-                token_fee_config.treasury,
-                vec![
-                    AccountMeta::new(token_wallet, false),
-                    AccountMeta::new_readonly(token::ID, false),
-                ],
-            )
-        } else {
-            (config.treasury, vec![])
-        };
+    /// Builds the request to the deployment described by the `env`,
+    /// e.g. a fork with custom seeds (see [`Env`]).
+    pub fn build_with_env<'a, C: Deref<Target = impl Signer> + Clone>(
+        self,
+        orao_vrf: &'a anchor_client::Program<C>,
+        env: &Env,
+    ) -> Result<anchor_client::RequestBuilder<'a, C>, anchor_client::ClientError> {
+        let network_state = get_network_state_with_env(orao_vrf, env)?;
+        let (treasury, remaining_accounts) =
+            request_fee_accounts(&network_state.config, self.token_wallet)?;
 
-        Ok(orao_vrf
-            .request()
-            .accounts(crate::accounts::Request {
-                payer: orao_vrf.payer(),
-                network_state: network_state_address,
-                treasury,
-                request: request_address,
-                system_program: system_program::ID,
-            })
-            .args(crate::instruction::Request { seed: self.seed })
-            .accounts(remaining_accounts))
+        Ok(self
+            .instructions(env, orao_vrf.payer(), treasury, &remaining_accounts)
+            .into_iter()
+            .fold(orao_vrf.request(), |builder, ix| builder.instruction(ix)))
+    }
+
+    /// Returns instructions of the request transaction – the compute budget instructions
+    /// (if any) followed by the `request` instruction.
+    fn instructions(
+        &self,
+        env: &Env,
+        payer: Pubkey,
+        treasury: Pubkey,
+        remaining_accounts: &[AccountMeta],
+    ) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(3);
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                micro_lamports,
+            ));
+        }
+        instructions.push(request_instruction(
+            env,
+            payer,
+            self.seed,
+            treasury,
+            remaining_accounts,
+        ));
+        instructions
+    }
+}
+
+/// Returns the treasury and the remaining accounts of a `request` instruction.
+///
+/// Token fee accounts are used if `token_wallet` is given.
+fn request_fee_accounts(
+    config: &NetworkConfiguration,
+    token_wallet: Option<Pubkey>,
+) -> Result<(Pubkey, Vec<AccountMeta>), ClientError> {
+    if let Some(token_wallet) = token_wallet {
+        let token_fee_config = config.token_fee_config.as_ref().ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(
+                "Token fee is not configured for the given VRF instance".to_string(),
+            ))
+        })?;
+        Ok((
+            token_fee_config.treasury,
+            vec![
+                AccountMeta::new(token_wallet, false),
+                AccountMeta::new_readonly(token::ID, false),
+            ],
+        ))
+    } else {
+        Ok((config.treasury, vec![]))
+    }
+}
+
+/// Builds a standalone `request` instruction.
+fn request_instruction(
+    env: &Env,
+    payer: Pubkey,
+    seed: [u8; 32],
+    treasury: Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = crate::accounts::Request {
+        payer,
+        network_state: env.network_state_account_address(),
+        treasury,
+        request: env.randomness_account_address(&seed),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: env.program_id(),
+        accounts,
+        data: crate::instruction::Request { seed }.data(),
     }
 }
 
@@ -334,8 +837,8 @@ impl FulfillBuilder {
         orao_vrf: &'a anchor_client::Program<C>,
         fullfill_authority: &Keypair,
     ) -> anchor_client::RequestBuilder<'a, C> {
-        let network_state_address = network_state_account_address();
-        let request_address = randomness_account_address(&self.seed);
+        let network_state_address = network_state_account_address_for(&orao_vrf.id());
+        let request_address = randomness_account_address_for(&orao_vrf.id(), &self.seed);
 
         let fullfill_authority =
             ed25519_dalek::Keypair::from_bytes(fullfill_authority.to_bytes().as_ref()).unwrap();
@@ -357,8 +860,106 @@ impl FulfillBuilder {
 }
 
 impl Randomness {
+    /// Decodes randomness request account of the VRF deployed at `program_id`.
+    ///
+    /// Fails with [`SdkError::UnexpectedAccountOwner`] if the account is not owned
+    /// by the program, see also [`Randomness::decode_from_bytes`]. Expects the Anchor
+    /// account layout, use [`Env::decode_randomness`] for deployments without
+    /// the discriminator.
+    pub fn decode_from_account(
+        program_id: &Pubkey,
+        account: &Account,
+    ) -> Result<Self, anchor_client::ClientError> {
+        if account.owner != *program_id {
+            return Err(SdkError::UnexpectedAccountOwner.into());
+        }
+        Ok(Self::decode_from_bytes(&account.data)?)
+    }
+
+    /// Decodes randomness request account data (Anchor account layout,
+    /// see [`Env::decode_randomness`] for raw borsh).
+    ///
+    /// The size is checked up front, so truncated or oversized data never panics:
+    ///
+    /// * fails with [`AccountDecodeError::AccountLayoutMismatch`] if the data size doesn't
+    ///   match [`RANDOMNESS_ACCOUNT_SIZE`], e.g. after a program upgrade changed the layout,
+    /// * fails with [`AccountDecodeError::DiscriminatorMismatch`] if the data is not
+    ///   a randomness account.
+    pub fn decode_from_bytes(data: &[u8]) -> Result<Self, AccountDecodeError> {
+        if data.len() != RANDOMNESS_ACCOUNT_SIZE {
+            return Err(AccountDecodeError::AccountLayoutMismatch {
+                expected: RANDOMNESS_ACCOUNT_SIZE,
+                found: data.len(),
+            });
+        }
+        let (discriminator, mut data) = data.split_at(8);
+        if discriminator != Self::DISCRIMINATOR {
+            return Err(AccountDecodeError::DiscriminatorMismatch);
+        }
+        Self::deserialize(&mut data).map_err(|_| AccountDecodeError::DidNotDeserialize)
+    }
+
+    /// Checks that this is the randomness of the requested `seed`.
+    ///
+    /// Fails with [`SdkError::SeedMismatch`] otherwise, so that randomness bound
+    /// to a different seed is never accepted.
+    pub fn check_seed(&self, seed: &[u8; 32]) -> Result<(), anchor_client::ClientError> {
+        if self.seed != *seed {
+            return Err(SdkError::SeedMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Returns fulfilled randomness (see [`Randomness::fulfilled`]).
+    ///
+    /// Fails with [`SdkError::RandomnessNotFulfilled`] if randomness is not yet fulfilled,
+    /// so that verification helpers never verify an all-zero placeholder.
+    pub fn check_fulfilled(&self) -> Result<&[u8; 64], anchor_client::ClientError> {
+        self.fulfilled()
+            .ok_or_else(|| SdkError::RandomnessNotFulfilled.into())
+    }
+
+    /// Checks that at least `threshold` fulfillment authorities have responded.
+    ///
+    /// Fails with [`SdkError::InsufficientResponders`] (carrying the threshold
+    /// and the actual number of responders) otherwise.
+    pub fn check_responders(&self, threshold: usize) -> Result<(), anchor_client::ClientError> {
+        if self.responder_count() < threshold {
+            return Err(
+                anchor_lang::error::Error::from(SdkError::InsufficientResponders)
+                    .with_values((threshold, self.responder_count()))
+                    .into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Verifies the response of a single known fulfillment authority – a single Ed25519
+    /// check of the seed signature, no transaction lookups.
+    ///
+    /// Returns `false` if the authority hasn't responded or the signature doesn't verify.
+    /// Note that this doesn't check the combined randomness, see [`Randomness::verify_offchain`].
+    pub fn verify_response(&self, authority: &Pubkey) -> bool {
+        self.responses
+            .iter()
+            .find(|response| response.pubkey == *authority)
+            .is_some_and(|response| {
+                DefaultSignatureVerifier.verify(&response.pubkey, &self.seed, &response.randomness)
+            })
+    }
+
     /// Performs offchain verification against the effective list of fulfillment authorities.
     pub fn verify_offchain(&self, fulfullment_authorities: &[Pubkey]) -> bool {
+        self.verify_offchain_with(fulfullment_authorities, DefaultSignatureVerifier)
+    }
+
+    /// Performs offchain verification (see [`Randomness::verify_offchain`])
+    /// using the given signature verification backend.
+    pub fn verify_offchain_with(
+        &self,
+        fulfullment_authorities: &[Pubkey],
+        verifier: impl SignatureVerifier,
+    ) -> bool {
         if !quorum(self.responses.len(), fulfullment_authorities.len()) {
             return false;
         }
@@ -369,9 +970,7 @@ impl Randomness {
                 return false;
             }
 
-            let sig = Signature::from(response.randomness);
-
-            if !sig.verify(response.pubkey.as_ref(), &self.seed) {
+            if !verifier.verify(&response.pubkey, &self.seed, &response.randomness) {
                 return false;
             }
 
@@ -381,3 +980,124 @@ impl Randomness {
         expected_randomness == self.randomness
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_sdk::{compute_budget, transaction::Transaction};
+    use anchor_lang::AccountSerialize;
+
+    use super::*;
+
+    #[test]
+    fn compute_budget_instructions_precede_request() {
+        let payer = Pubkey::new_unique();
+        let seed = [1; 32];
+        let instructions = RequestBuilder::new(seed)
+            .with_compute_unit_price(5_000)
+            .with_compute_unit_limit(20_000)
+            .instructions(&Env::default(), payer, Pubkey::new_unique(), &[]);
+
+        let transaction = Transaction::new_with_payer(&instructions, Some(&payer));
+        let message = &transaction.message;
+        let program_ids = message
+            .instructions
+            .iter()
+            .map(|ix| *ix.program_id(&message.account_keys))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            program_ids,
+            [compute_budget::id(), compute_budget::id(), crate::id()]
+        );
+        assert_eq!(
+            message.instructions[0].data,
+            ComputeBudgetInstruction::set_compute_unit_limit(20_000).data
+        );
+        assert_eq!(
+            message.instructions[1].data,
+            ComputeBudgetInstruction::set_compute_unit_price(5_000).data
+        );
+        assert_eq!(
+            message.instructions[2].data,
+            crate::instruction::Request { seed }.data()
+        );
+    }
+
+    fn randomness_account_data() -> (Randomness, Vec<u8>) {
+        let randomness = Randomness {
+            seed: [1; 32],
+            randomness: [2; 64],
+            responses: vec![crate::state::RandomnessResponse {
+                pubkey: Pubkey::new_unique(),
+                randomness: [3; 64],
+            }],
+        };
+        let mut data = Vec::new();
+        randomness.try_serialize(&mut data).unwrap();
+        data.resize(RANDOMNESS_ACCOUNT_SIZE, 0);
+        (randomness, data)
+    }
+
+    #[test]
+    fn decode_from_bytes_accepts_exact_size() {
+        let (randomness, data) = randomness_account_data();
+        assert_eq!(Randomness::decode_from_bytes(&data), Ok(randomness));
+    }
+
+    #[test]
+    fn decode_from_bytes_rejects_truncated_data() {
+        let (_, data) = randomness_account_data();
+        for len in [0, 7, 8, 100, RANDOMNESS_ACCOUNT_SIZE - 1] {
+            assert_eq!(
+                Randomness::decode_from_bytes(&data[..len]),
+                Err(AccountDecodeError::AccountLayoutMismatch {
+                    expected: RANDOMNESS_ACCOUNT_SIZE,
+                    found: len,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn decode_from_bytes_rejects_oversized_data() {
+        let (_, mut data) = randomness_account_data();
+        data.push(0);
+        assert_eq!(
+            Randomness::decode_from_bytes(&data),
+            Err(AccountDecodeError::AccountLayoutMismatch {
+                expected: RANDOMNESS_ACCOUNT_SIZE,
+                found: RANDOMNESS_ACCOUNT_SIZE + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_from_bytes_rejects_foreign_discriminator() {
+        let (_, mut data) = randomness_account_data();
+        data[..8].copy_from_slice(&NetworkState::DISCRIMINATOR);
+        assert_eq!(
+            Randomness::decode_from_bytes(&data),
+            Err(AccountDecodeError::DiscriminatorMismatch)
+        );
+    }
+
+    #[test]
+    fn layout_mismatch_converts_to_unexpected_account_size() {
+        let err = anchor_client::ClientError::from(AccountDecodeError::AccountLayoutMismatch {
+            expected: RANDOMNESS_ACCOUNT_SIZE,
+            found: 0,
+        });
+        assert!(SdkError::UnexpectedAccountSize.matches(&err));
+    }
+
+    #[test]
+    fn no_compute_budget_instructions_by_default() {
+        let instructions = RequestBuilder::new([1; 32]).instructions(
+            &Env::default(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &[],
+        );
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].program_id, crate::id());
+    }
+}
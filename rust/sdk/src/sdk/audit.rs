@@ -0,0 +1,129 @@
+use anchor_client::{
+    solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS, solana_sdk::signer::Signer,
+};
+
+use crate::{randomness_account_address_for, state::Randomness};
+
+use std::ops::Deref;
+
+use super::{SeedRepr, VerificationContext};
+
+/// Summary of a batch audit (see [`audit_batch`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchAuditReport {
+    /// Number of audited seeds.
+    pub total: usize,
+    /// Number of seeds that were never requested.
+    pub not_requested: usize,
+    /// Number of requests waiting for fulfillment.
+    pub pending: usize,
+    /// Number of fulfilled requests.
+    pub fulfilled: usize,
+    /// Number of fulfilled requests that passed the verification.
+    pub verified: usize,
+    /// Details of the requests that failed the audit.
+    pub failures: Vec<AuditFailure>,
+}
+
+/// Request that failed the audit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditFailure {
+    /// Request seed.
+    pub seed: SeedRepr,
+    /// Why the audit failed.
+    pub reason: AuditFailureReason,
+}
+
+/// Reason of an [`AuditFailure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditFailureReason {
+    /// The randomness account couldn't be decoded.
+    InvalidAccount(String),
+    /// The fulfilled randomness didn't pass the offchain verification
+    /// (see [`VerificationContext::verify_offchain`]).
+    VerificationFailed,
+}
+
+/// Audits randomness of the given seeds against the effective fulfillment authorities.
+///
+/// Fetches all the randomness accounts, verifies every fulfilled randomness offchain
+/// and aggregates the outcome into a single report. Use [`VerificationContext::audit_batch`]
+/// to audit against a fixed set of fulfillment authorities.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seeds: Vec<[u8; 32]> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let report = orao_solana_vrf::audit_batch(&program, &seeds)?;
+/// println!("{} of {} verified", report.verified, report.total);
+/// for failure in report.failures {
+///     println!("{}: {:?}", failure.seed.base58, failure.reason);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn audit_batch<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seeds: &[[u8; 32]],
+) -> Result<BatchAuditReport, anchor_client::ClientError> {
+    VerificationContext::load(orao_vrf)?.audit_batch(orao_vrf, seeds)
+}
+
+impl VerificationContext {
+    /// Audits randomness of the given seeds against the fulfillment authorities
+    /// (see [`audit_batch`]).
+    pub fn audit_batch<C: Deref<Target = impl Signer> + Clone>(
+        &self,
+        orao_vrf: &anchor_client::Program<C>,
+        seeds: &[[u8; 32]],
+    ) -> Result<BatchAuditReport, anchor_client::ClientError> {
+        let mut report = BatchAuditReport {
+            total: seeds.len(),
+            ..Default::default()
+        };
+
+        for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses = chunk
+                .iter()
+                .map(|seed| randomness_account_address_for(&orao_vrf.id(), seed))
+                .collect::<Vec<_>>();
+            let accounts = orao_vrf.rpc().get_multiple_accounts(&addresses)?;
+            for (seed, account) in chunk.iter().zip(accounts) {
+                let Some(account) = account else {
+                    report.not_requested += 1;
+                    continue;
+                };
+                let failure = |reason| AuditFailure {
+                    seed: SeedRepr::from(*seed),
+                    reason,
+                };
+                let randomness = match Randomness::decode_from_account(&orao_vrf.id(), &account) {
+                    Ok(randomness) => randomness,
+                    Err(err) => {
+                        let reason = AuditFailureReason::InvalidAccount(err.to_string());
+                        report.failures.push(failure(reason));
+                        continue;
+                    }
+                };
+                if randomness.fulfilled().is_none() {
+                    report.pending += 1;
+                    continue;
+                }
+                report.fulfilled += 1;
+                if self.verify_offchain(&randomness) {
+                    report.verified += 1;
+                } else {
+                    report
+                        .failures
+                        .push(failure(AuditFailureReason::VerificationFailed));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,350 @@
+use anchor_client::solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
+use anchor_client::solana_sdk::{
+    instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE, signature::Signature,
+    signer::Signer,
+};
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_spl::token;
+
+use crate::state::Randomness;
+
+use std::ops::Deref;
+
+use super::{get_network_state_with_env, request_fee_accounts, request_instruction, Env};
+
+/// Defines how [`BatchRequestBuilder`] handles seeds that are already in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnExisting {
+    /// Leave the seed out of the batch.
+    #[default]
+    Skip,
+    /// Fail the whole batch with [`crate::Error::SeedAlreadyInUse`].
+    Error,
+    /// Leave the seed out of the batch and return its current randomness state
+    /// (see [`BatchRequest::existing`]).
+    ReturnExisting,
+}
+
+/// Requests randomness for the given seeds packing as many requests as possible
+/// into every transaction (see [`BatchRequestBuilder`]).
+///
+/// Seeds already in use are skipped, and the network state is fetched once for the whole
+/// batch. Transactions are sent one after another; returns their signatures.
+/// A failed transaction fails the call, leaving the remaining seeds unrequested.
+///
+/// See `nonblocking::VrfClient::request_batch` for the async counterpart
+/// (requires `async` feature to be enabled).
+pub fn request_randomness_batch<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seeds: Vec<[u8; 32]>,
+) -> Result<Vec<Signature>, anchor_client::ClientError> {
+    BatchRequestBuilder::new(seeds)
+        .build(orao_vrf)?
+        .requests
+        .into_iter()
+        .map(|request| request.send())
+        .collect()
+}
+
+/// Returns the maximum number of `request` instructions that fit in a single transaction.
+///
+/// The number is limited by the transaction size (see [`PACKET_DATA_SIZE`])
+/// of a transaction signed by the payer alone. Note that the instructions also share
+/// the compute budget of the transaction – at most 1.4M compute units in total
+/// (by default 200k per instruction up to that cap). This does not account for it:
+/// only about a dozen requests fit in a transaction, which still leaves over
+/// 100k compute units per request.
+///
+/// Set `pay_with_token` if fees are paid with SPL token (see [`BatchRequestBuilder::pay_with_token`]).
+pub fn max_requests_per_transaction(pay_with_token: bool) -> usize {
+    // distinct keys, so that every account takes up space in the transaction
+    let payer = Pubkey::new_from_array([1; 32]);
+    let treasury = Pubkey::new_from_array([2; 32]);
+    let remaining_accounts = if pay_with_token {
+        vec![
+            AccountMeta::new(Pubkey::new_from_array([3; 32]), false),
+            AccountMeta::new_readonly(token::ID, false),
+        ]
+    } else {
+        vec![]
+    };
+
+    let mut instructions = Vec::new();
+    loop {
+        let mut seed = [u8::MAX; 32];
+        seed[..8].copy_from_slice(&(instructions.len() as u64).to_le_bytes());
+        instructions.push(request_instruction(
+            &Env::default(),
+            payer,
+            seed,
+            treasury,
+            &remaining_accounts,
+        ));
+
+        if transaction_size(&instructions, &payer) > PACKET_DATA_SIZE {
+            break instructions.len() - 1;
+        }
+    }
+}
+
+/// Returns the serialized size of a transaction with the given instructions.
+fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> usize {
+    let message = Message::new(instructions, Some(payer));
+    // one byte of the signature count (compact-u16 for up to 127 signatures)
+    // followed by the signatures
+    1 + 64 * message.header.num_required_signatures as usize + message.serialize().len()
+}
+
+/// Batched `request` instruction builder.
+///
+/// Builds transactions with one `request` instruction per seed. Seeds are packed into
+/// as few transactions as possible – instructions are added to a transaction
+/// as long as its serialized size stays within the limit
+/// (see [`BatchRequestBuilder::max_transaction_size`]).
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use orao_solana_vrf::{BatchRequestBuilder, OnExisting};
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let seeds = vec![rand::random(), rand::random()];
+/// let batch = BatchRequestBuilder::new(seeds)
+///     .on_existing(OnExisting::Error)
+///     .build(&program)?;
+///
+/// for request in batch.requests {
+///     println!("Your transaction is {}", request.send()?);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default)]
+pub struct BatchRequestBuilder {
+    seeds: Vec<[u8; 32]>,
+    token_wallet: Option<Pubkey>,
+    on_existing: OnExisting,
+    max_transaction_size: Option<usize>,
+}
+
+/// Batched randomness request built by [`BatchRequestBuilder`].
+pub struct BatchRequest<'a, C> {
+    /// Requests for the seeds that are not yet in use – one per transaction.
+    ///
+    /// Empty if there is nothing to request.
+    pub requests: Vec<anchor_client::RequestBuilder<'a, C>>,
+    /// Seeds requested by each of the [`BatchRequest::requests`] (in the same order).
+    pub groups: Vec<Vec<[u8; 32]>>,
+    /// Seeds that are already in use along with the current randomness state.
+    ///
+    /// Only populated with [`OnExisting::ReturnExisting`].
+    pub existing: Vec<([u8; 32], Randomness)>,
+}
+
+impl BatchRequestBuilder {
+    /// Creates a new builder with the given seeds. Fees are paid with lamports by default.
+    ///
+    /// Duplicate seeds are requested once.
+    pub fn new(seeds: Vec<[u8; 32]>) -> Self {
+        Self {
+            seeds,
+            ..Default::default()
+        }
+    }
+
+    /// Pay fees with SPL token using given token wallet address.
+    ///
+    /// Instruction could fail if token fee is not configured for the contract.
+    pub fn pay_with_token(mut self, token_wallet: Pubkey) -> Self {
+        self.token_wallet = Some(token_wallet);
+        self
+    }
+
+    /// Change the way seeds that are already in use are handled
+    /// (defaults to [`OnExisting::Skip`]).
+    pub fn on_existing(mut self, on_existing: OnExisting) -> Self {
+        self.on_existing = on_existing;
+        self
+    }
+
+    /// Change the maximum serialized transaction size (defaults to [`PACKET_DATA_SIZE`]).
+    ///
+    /// Limits above [`PACKET_DATA_SIZE`] produce transactions that can't be sent.
+    pub fn max_transaction_size(mut self, max_transaction_size: usize) -> Self {
+        self.max_transaction_size = Some(max_transaction_size);
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// Fails with a custom client error if a single request doesn't fit
+    /// in the maximum transaction size.
+    pub fn build<'a, C: Deref<Target = impl Signer> + Clone>(
+        self,
+        orao_vrf: &'a anchor_client::Program<C>,
+    ) -> Result<BatchRequest<'a, C>, anchor_client::ClientError> {
+        self.build_with_env(orao_vrf, &Env::new(orao_vrf.id()))
+    }
+
+    /// Builds the request to the deployment described by the `env`,
+    /// e.g. a fork with custom seeds (see [`Env`] and [`BatchRequestBuilder::build`]).
+    pub fn build_with_env<'a, C: Deref<Target = impl Signer> + Clone>(
+        self,
+        orao_vrf: &'a anchor_client::Program<C>,
+        env: &Env,
+    ) -> Result<BatchRequest<'a, C>, anchor_client::ClientError> {
+        let network_state = get_network_state_with_env(orao_vrf, env)?;
+        let (treasury, remaining_accounts) =
+            request_fee_accounts(&network_state.config, self.token_wallet)?;
+
+        let mut seeds = self.seeds;
+        let mut seen = std::collections::HashSet::new();
+        seeds.retain(|seed| seen.insert(*seed));
+
+        let mut to_request = Vec::with_capacity(seeds.len());
+        let mut existing = Vec::new();
+        for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses = chunk
+                .iter()
+                .map(|seed| env.randomness_account_address(seed))
+                .collect::<Vec<_>>();
+            let accounts = orao_vrf.rpc().get_multiple_accounts(&addresses)?;
+            for (seed, account) in chunk.iter().zip(accounts) {
+                let Some(account) = account else {
+                    to_request.push(*seed);
+                    continue;
+                };
+                match self.on_existing {
+                    OnExisting::Skip => (),
+                    OnExisting::Error => return Err(crate::Error::SeedAlreadyInUse.into()),
+                    OnExisting::ReturnExisting => {
+                        let randomness = env.decode_randomness(&account)?;
+                        existing.push((*seed, randomness));
+                    }
+                }
+            }
+        }
+
+        let groups = pack_requests(
+            env,
+            orao_vrf.payer(),
+            to_request,
+            treasury,
+            &remaining_accounts,
+            self.max_transaction_size.unwrap_or(PACKET_DATA_SIZE),
+        )?;
+
+        let (groups, requests) = groups
+            .into_iter()
+            .map(|(seeds, instructions)| {
+                let request = instructions
+                    .into_iter()
+                    .fold(orao_vrf.request(), |request, ix| request.instruction(ix));
+                (seeds, request)
+            })
+            .unzip();
+
+        Ok(BatchRequest {
+            requests,
+            groups,
+            existing,
+        })
+    }
+}
+
+/// Seeds requested by a transaction along with its instructions.
+pub(super) type RequestGroup = (Vec<[u8; 32]>, Vec<Instruction>);
+
+/// Packs `request` instructions of the given seeds into as few transactions as possible
+/// (see [`BatchRequestBuilder`]).
+///
+/// Returns seeds along with instructions of every transaction. Fails with a custom
+/// client error if a single request doesn't fit in `max_size`.
+pub(super) fn pack_requests(
+    env: &Env,
+    payer: Pubkey,
+    seeds: impl IntoIterator<Item = [u8; 32]>,
+    treasury: Pubkey,
+    remaining_accounts: &[AccountMeta],
+    max_size: usize,
+) -> Result<Vec<RequestGroup>, anchor_client::ClientError> {
+    let mut groups: Vec<RequestGroup> = Vec::new();
+    for seed in seeds {
+        let instruction = request_instruction(env, payer, seed, treasury, remaining_accounts);
+        if let Some((seeds, instructions)) = groups.last_mut() {
+            instructions.push(instruction.clone());
+            if transaction_size(instructions, &payer) <= max_size {
+                seeds.push(seed);
+                continue;
+            }
+            instructions.pop();
+        }
+        if transaction_size(std::slice::from_ref(&instruction), &payer) > max_size {
+            return Err(ClientError::from(ClientErrorKind::Custom(format!(
+                "Request doesn't fit in a transaction of {} bytes",
+                max_size
+            )))
+            .into());
+        }
+        groups.push((vec![seed], vec![instruction]));
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeds(count: u8) -> Vec<[u8; 32]> {
+        (1..=count).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn packs_requests_up_to_the_transaction_size() {
+        let payer = Pubkey::new_from_array([1; 32]);
+        let treasury = Pubkey::new_from_array([2; 32]);
+        let remaining_accounts = [
+            AccountMeta::new(Pubkey::new_from_array([3; 32]), false),
+            AccountMeta::new_readonly(token::ID, false),
+        ];
+
+        let groups = pack_requests(
+            &Env::default(),
+            payer,
+            seeds(20),
+            treasury,
+            &remaining_accounts,
+            PACKET_DATA_SIZE,
+        )
+        .unwrap();
+
+        let per_transaction = max_requests_per_transaction(true);
+        assert_eq!(groups.len(), 20_usize.div_ceil(per_transaction));
+        assert!(groups.iter().all(|(seeds, instructions)| {
+            seeds.len() == instructions.len()
+                && seeds.len() <= per_transaction
+                && transaction_size(instructions, &payer) <= PACKET_DATA_SIZE
+        }));
+        let packed = groups.into_iter().flat_map(|(seeds, _)| seeds);
+        assert_eq!(packed.collect::<Vec<_>>(), seeds(20));
+    }
+
+    #[test]
+    fn max_requests_per_transaction_fits_the_compute_budget() {
+        assert_eq!(max_requests_per_transaction(false), 12);
+        assert_eq!(max_requests_per_transaction(true), 11);
+        assert!(max_requests_per_transaction(false) * 100_000 <= 1_400_000);
+    }
+
+    #[test]
+    fn fails_if_a_request_does_not_fit() {
+        let payer = Pubkey::new_from_array([1; 32]);
+        let treasury = Pubkey::new_from_array([2; 32]);
+        assert!(pack_requests(&Env::default(), payer, seeds(1), treasury, &[], 100).is_err());
+    }
+}
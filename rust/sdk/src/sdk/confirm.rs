@@ -0,0 +1,259 @@
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_config::RpcTransactionConfig,
+        rpc_request::MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, signer::Signer},
+};
+use anchor_lang::prelude::Pubkey;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiTransactionEncoding, UiTransactionTokenBalance,
+};
+
+use crate::{
+    network_state_account_address_for, randomness_account_address_for,
+    state::{NetworkState, Randomness},
+    SdkError,
+};
+
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+/// Interval between signature status queries.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Confirmations reported for finalized (rooted) transactions by [`request_confirmations`].
+pub const FINALIZED_CONFIRMATIONS: usize = 32;
+
+/// Waits for the given transactions to be confirmed.
+///
+/// Queries statuses of all the pending transactions in a single `getSignatureStatuses`
+/// call per round (per 256 transactions), until every transaction is either confirmed
+/// or failed, or the `timeout` elapses.
+///
+/// Returns `true` for every transaction that succeeded at the client commitment level,
+/// and `false` for every failed or not yet confirmed transaction (in the given order).
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use std::time::Duration;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let signatures: Vec<solana_sdk::signature::Signature> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let confirmed =
+///     orao_solana_vrf::confirm_transactions(&program, &signatures, Duration::from_secs(60))?;
+/// # Ok(()) }
+/// ```
+pub fn confirm_transactions<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    signatures: &[Signature],
+    timeout: Duration,
+) -> Result<Vec<bool>, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let commitment = rpc.commitment();
+    let deadline = Instant::now() + timeout;
+
+    let mut outcomes: Vec<Option<bool>> = vec![None; signatures.len()];
+    loop {
+        let pending = (0..signatures.len())
+            .filter(|i| outcomes[*i].is_none())
+            .collect::<Vec<_>>();
+        for chunk in pending.chunks(MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS) {
+            let query = chunk.iter().map(|i| signatures[*i]).collect::<Vec<_>>();
+            let statuses = rpc.get_signature_statuses(&query)?.value;
+            for (i, status) in chunk.iter().zip(statuses) {
+                match status {
+                    Some(status) if status.err.is_some() => outcomes[*i] = Some(false),
+                    Some(status) if status.satisfies_commitment(commitment) => {
+                        outcomes[*i] = Some(true)
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if outcomes.iter().all(Option::is_some) || Instant::now() >= deadline {
+            break;
+        }
+        std::thread::sleep(STATUS_POLL_INTERVAL);
+    }
+
+    Ok(outcomes
+        .into_iter()
+        .map(|outcome| outcome.unwrap_or(false))
+        .collect())
+}
+
+/// Returns the number of confirmations of the given transaction (e.g. a request transaction).
+///
+/// Finalized transactions report [`FINALIZED_CONFIRMATIONS`] – more than any
+/// not yet finalized transaction. Returns `None` if the transaction is not found
+/// among recent transactions.
+pub fn request_confirmations<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    signature: &Signature,
+) -> Result<Option<usize>, anchor_client::ClientError> {
+    let status = orao_vrf
+        .rpc()
+        .get_signature_statuses(&[*signature])?
+        .value
+        .into_iter()
+        .next()
+        .flatten();
+    Ok(status.map(|status| status.confirmations.unwrap_or(FINALIZED_CONFIRMATIONS)))
+}
+
+/// Waits until the fulfilled randomness is observed at the finalized commitment level.
+///
+/// Lets latency-sensitive flows proceed optimistically on a fulfillment observed
+/// at a lower commitment level and then reconcile against finality.
+/// Fails with [`SdkError::Timeout`] if the fulfillment is not finalized within the `timeout`.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use std::time::Duration;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let randomness = orao_solana_vrf::FulfillmentWaiter::new(seed).wait(&program)?;
+/// // proceed optimistically, then
+/// orao_solana_vrf::await_finalization(&program, &seed, Duration::from_secs(60))?;
+/// # Ok(()) }
+/// ```
+pub fn await_finalization<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    timeout: Duration,
+) -> Result<Randomness, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let address = randomness_account_address_for(&orao_vrf.id(), seed);
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let account = rpc
+            .get_account_with_commitment(&address, CommitmentConfig::finalized())?
+            .value;
+        if let Some(account) = account {
+            let randomness = Randomness::decode_from_account(&orao_vrf.id(), &account)?;
+            if randomness.fulfilled().is_some() {
+                return Ok(randomness);
+            }
+        }
+        if Instant::now() >= deadline {
+            return Err(SdkError::Timeout.into());
+        }
+        std::thread::sleep(STATUS_POLL_INTERVAL);
+    }
+}
+
+/// Fees credited to the VRF treasuries by a request transaction (see [`treasury_credit`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TreasuryCredit {
+    /// Lamports credited to the treasury.
+    pub lamports: u64,
+    /// Tokens (in base units) credited to the token fee treasury.
+    ///
+    /// Zero if token fee is not configured.
+    pub tokens: u64,
+}
+
+/// Returns the exact fees credited to the treasuries by the given confirmed
+/// request transaction.
+///
+/// Reads the balance changes recorded in the transaction metadata rather than
+/// the configured fee, so this gives ground-truth fee accounting. Note that
+/// the treasuries are taken from the current VRF configuration.
+/// Fails with a custom client error if the transaction metadata is not available.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let tx = orao_solana_vrf::RequestBuilder::new(rand::random())
+///     .build(&program)?
+///     .send()?;
+/// let credit = orao_solana_vrf::treasury_credit(&program, &tx)?;
+/// println!("Paid {} lamports to the treasury", credit.lamports);
+/// # Ok(()) }
+/// ```
+pub fn treasury_credit<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    signature: &Signature,
+) -> Result<TreasuryCredit, anchor_client::ClientError> {
+    let network_state: NetworkState =
+        orao_vrf.account(network_state_account_address_for(&orao_vrf.id()))?;
+    let config = network_state.config;
+
+    let confirmed = orao_vrf.rpc().get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let meta = confirmed.transaction.meta.ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(format!(
+            "Transaction {} has no metadata",
+            signature
+        )))
+    })?;
+    let transaction = confirmed.transaction.transaction.decode().ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(format!(
+            "Transaction {} is not in a binary encoding",
+            signature
+        )))
+    })?;
+
+    // the request instruction never touches lookup tables, so the treasuries are static keys
+    let keys = transaction.message.static_account_keys();
+    let index_of = |address: &Pubkey| keys.iter().position(|key| key == address);
+
+    let lamports = index_of(&config.treasury)
+        .and_then(|i| {
+            Some(
+                meta.post_balances
+                    .get(i)?
+                    .saturating_sub(*meta.pre_balances.get(i)?),
+            )
+        })
+        .unwrap_or(0);
+
+    let tokens = match &config.token_fee_config {
+        Some(token_fee_config) => match index_of(&token_fee_config.treasury) {
+            Some(i) => token_amount(&meta.post_token_balances, i)
+                .saturating_sub(token_amount(&meta.pre_token_balances, i)),
+            None => 0,
+        },
+        None => 0,
+    };
+
+    Ok(TreasuryCredit { lamports, tokens })
+}
+
+/// Returns the token balance of the account at the given index (zero if missing).
+fn token_amount(balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>, index: usize) -> u64 {
+    let OptionSerializer::Some(balances) = balances else {
+        return 0;
+    };
+    balances
+        .iter()
+        .find(|balance| balance.account_index as usize == index)
+        .and_then(|balance| balance.ui_token_amount.amount.parse().ok())
+        .unwrap_or(0)
+}
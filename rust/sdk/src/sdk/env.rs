@@ -0,0 +1,302 @@
+use anchor_client::solana_sdk::account::Account;
+use anchor_lang::{
+    error::{Error, ErrorCode},
+    prelude::Pubkey,
+    AccountDeserialize, AnchorDeserialize,
+};
+
+use crate::{
+    state::{NetworkState, Randomness},
+    AccountDecodeError, SdkError, CONFIG_ACCOUNT_SEED, RANDOMNESS_ACCOUNT_SEED,
+    RANDOMNESS_ACCOUNT_SIZE,
+};
+
+use super::validate_seed_prefix;
+
+/// VRF deployment environment – the program id along with the PDA seed prefixes.
+///
+/// Defaults match the ORAO deployment. Override them to use a fork that changed
+/// the program id or the seeds (e.g. a program deployed to `solana-test-validator`):
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use orao_solana_vrf::Env;
+///
+/// # let program_id: anchor_client::solana_sdk::pubkey::Pubkey = panic!();
+/// let env = Env::new(program_id)
+///     .with_config_account_seed(b"my-vrf-network-configuration".to_vec())?
+///     .with_randomness_account_seed(b"my-vrf-randomness-request".to_vec())?;
+/// # Ok(()) }
+/// ```
+///
+/// Pass it to [`crate::RequestBuilder::build_with_env`],
+/// [`crate::BatchRequestBuilder::build_with_env`] or `nonblocking::VrfClient::with_env`
+/// (requires `async` feature). Note that the transaction verification helpers
+/// (e.g. [`crate::verify_fulfillment_with`]) assume the default seeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Env {
+    program_id: Pubkey,
+    config_account_seed: Vec<u8>,
+    randomness_account_seed: Vec<u8>,
+    has_discriminator: bool,
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        Self::new(crate::id())
+    }
+}
+
+impl Env {
+    /// Creates an environment of the VRF deployed at `program_id` using the default seeds.
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            config_account_seed: CONFIG_ACCOUNT_SEED.to_vec(),
+            randomness_account_seed: RANDOMNESS_ACCOUNT_SEED.to_vec(),
+            has_discriminator: true,
+        }
+    }
+
+    /// Change the VRF program id.
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    /// Change the seed of the network state account (see [`validate_seed_prefix`]).
+    pub fn with_config_account_seed(
+        mut self,
+        seed: Vec<u8>,
+    ) -> Result<Self, anchor_client::ClientError> {
+        validate_seed_prefix(&seed)?;
+        self.config_account_seed = seed;
+        Ok(self)
+    }
+
+    /// Change the seed prefix of randomness accounts (see [`validate_seed_prefix`]).
+    pub fn with_randomness_account_seed(
+        mut self,
+        seed: Vec<u8>,
+    ) -> Result<Self, anchor_client::ClientError> {
+        validate_seed_prefix(&seed)?;
+        self.randomness_account_seed = seed;
+        Ok(self)
+    }
+
+    /// Change whether accounts start with the 8-byte Anchor discriminator
+    /// (the default) or are raw borsh.
+    ///
+    /// Honored by [`Env::decode_randomness`], `nonblocking::VrfClient` and the blocking
+    /// `*_with_env` helpers (e.g. [`crate::get_randomness_with_env`]). Other blocking
+    /// helpers expect the Anchor account layout.
+    pub fn with_discriminator(mut self, has_discriminator: bool) -> Self {
+        self.has_discriminator = has_discriminator;
+        self
+    }
+
+    /// Returns the VRF program id.
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// Returns the seed of the network state account.
+    pub fn config_account_seed(&self) -> &[u8] {
+        &self.config_account_seed
+    }
+
+    /// Returns the seed prefix of randomness accounts.
+    pub fn randomness_account_seed(&self) -> &[u8] {
+        &self.randomness_account_seed
+    }
+
+    /// Returns whether accounts start with the 8-byte Anchor discriminator.
+    pub fn has_discriminator(&self) -> bool {
+        self.has_discriminator
+    }
+
+    /// Decodes network state account of this deployment.
+    ///
+    /// Fails with [`SdkError::UnexpectedAccountOwner`] if the account is not owned
+    /// by the program.
+    pub fn decode_network_state(
+        &self,
+        account: &Account,
+    ) -> Result<NetworkState, anchor_client::ClientError> {
+        if account.owner != self.program_id {
+            return Err(SdkError::UnexpectedAccountOwner.into());
+        }
+        if self.has_discriminator {
+            return Ok(NetworkState::try_deserialize(&mut &account.data[..])?);
+        }
+        Ok(NetworkState::deserialize(&mut &account.data[..])
+            .map_err(|_| Error::from(ErrorCode::AccountDidNotDeserialize))?)
+    }
+
+    /// Decodes randomness request account of this deployment
+    /// (see [`Randomness::decode_from_account`]).
+    ///
+    /// Accounts without the discriminator are expected to be
+    /// [`RANDOMNESS_ACCOUNT_SIZE`] minus 8 bytes long.
+    pub fn decode_randomness(
+        &self,
+        account: &Account,
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        if self.has_discriminator {
+            return Randomness::decode_from_account(&self.program_id, account);
+        }
+        if account.owner != self.program_id {
+            return Err(SdkError::UnexpectedAccountOwner.into());
+        }
+        let expected_size = RANDOMNESS_ACCOUNT_SIZE - 8;
+        if account.data.len() != expected_size {
+            return Err(AccountDecodeError::AccountLayoutMismatch {
+                expected: expected_size,
+                found: account.data.len(),
+            }
+            .into());
+        }
+        Ok(Randomness::deserialize(&mut &account.data[..])
+            .map_err(|_| Error::from(ErrorCode::AccountDidNotDeserialize))?)
+    }
+
+    /// Returns network state account address.
+    pub fn network_state_account_address(&self) -> Pubkey {
+        if self.config_account_seed == CONFIG_ACCOUNT_SEED {
+            // cached for the default program id
+            return crate::network_state_account_address_for(&self.program_id);
+        }
+        Pubkey::find_program_address(&[&self.config_account_seed], &self.program_id).0
+    }
+
+    /// Returns randomness account address for the given seed.
+    pub fn randomness_account_address(&self, seed: &[u8; 32]) -> Pubkey {
+        self.randomness_account_address_with_bump(seed).0
+    }
+
+    /// Returns randomness account address along with its bump for the given seed
+    /// (see [`crate::randomness_account_address_with_bump_for`]).
+    pub fn randomness_account_address_with_bump(&self, seed: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[&self.randomness_account_seed, &seed[..]],
+            &self.program_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AccountSerialize, AnchorSerialize};
+
+    use crate::state::{NetworkConfiguration, RandomnessResponse};
+
+    use super::*;
+
+    fn account(data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1,
+            data,
+            owner: crate::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn randomness() -> Randomness {
+        Randomness {
+            seed: [1; 32],
+            randomness: [2; 64],
+            responses: vec![RandomnessResponse {
+                pubkey: Pubkey::new_from_array([4; 32]),
+                randomness: [3; 64],
+            }],
+        }
+    }
+
+    fn network_state() -> NetworkState {
+        NetworkState {
+            config: NetworkConfiguration {
+                authority: Pubkey::new_from_array([5; 32]),
+                treasury: Pubkey::new_from_array([6; 32]),
+                request_fee: 42,
+                fulfillment_authorities: vec![
+                    Pubkey::new_from_array([7; 32]),
+                    Pubkey::new_from_array([8; 32]),
+                ],
+                token_fee_config: None,
+            },
+            num_received: 7,
+        }
+    }
+
+    #[test]
+    fn decodes_randomness_with_discriminator() {
+        let mut data = Vec::new();
+        randomness().try_serialize(&mut data).unwrap();
+        data.resize(RANDOMNESS_ACCOUNT_SIZE, 0);
+        let account = account(data);
+
+        let env = Env::default();
+        assert_eq!(env.decode_randomness(&account).unwrap(), randomness());
+        assert!(env
+            .with_discriminator(false)
+            .decode_randomness(&account)
+            .is_err());
+    }
+
+    #[test]
+    fn decodes_randomness_without_discriminator() {
+        let mut data = randomness().try_to_vec().unwrap();
+        data.resize(RANDOMNESS_ACCOUNT_SIZE - 8, 0);
+        let account = account(data);
+
+        let env = Env::default().with_discriminator(false);
+        assert_eq!(env.decode_randomness(&account).unwrap(), randomness());
+        assert!(Env::default().decode_randomness(&account).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_size_randomness_without_discriminator() {
+        let env = Env::default().with_discriminator(false);
+        for len in [0, RANDOMNESS_ACCOUNT_SIZE - 9, RANDOMNESS_ACCOUNT_SIZE] {
+            let err = env.decode_randomness(&account(vec![0; len])).unwrap_err();
+            assert!(SdkError::UnexpectedAccountSize.matches(&err));
+        }
+    }
+
+    #[test]
+    fn decodes_network_state_with_discriminator() {
+        let mut data = Vec::new();
+        network_state().try_serialize(&mut data).unwrap();
+
+        let decoded = Env::default().decode_network_state(&account(data)).unwrap();
+        assert_eq!(
+            decoded.try_to_vec().unwrap(),
+            network_state().try_to_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn decodes_network_state_without_discriminator() {
+        let data = network_state().try_to_vec().unwrap();
+
+        let decoded = Env::default()
+            .with_discriminator(false)
+            .decode_network_state(&account(data.clone()))
+            .unwrap();
+        assert_eq!(decoded.try_to_vec().unwrap(), data);
+        assert!(Env::default().decode_network_state(&account(data)).is_err());
+    }
+
+    #[test]
+    fn rejects_accounts_of_other_programs() {
+        let mut account = account(vec![0; RANDOMNESS_ACCOUNT_SIZE - 8]);
+        account.owner = Pubkey::new_unique();
+        let env = Env::default().with_discriminator(false);
+
+        let err = env.decode_randomness(&account).unwrap_err();
+        assert!(SdkError::UnexpectedAccountOwner.matches(&err));
+        let err = env.decode_network_state(&account).unwrap_err();
+        assert!(SdkError::UnexpectedAccountOwner.matches(&err));
+    }
+}
@@ -0,0 +1,425 @@
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+        rpc_request::MAX_MULTIPLE_ACCOUNTS,
+        rpc_response::RpcConfirmedTransactionStatusWithSignature,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig, signature::Signature, signer::Signer,
+        transaction::VersionedTransaction,
+    },
+};
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::{randomness_account_address_for, state::Randomness};
+
+use std::ops::Deref;
+
+use super::{
+    fulfill_responses, get_randomness, randomness_signatures, request_transaction, Env,
+    FulfillmentScan, SignedMessage,
+};
+
+/// Maximum page size of the `getSignaturesForAddress` RPC method.
+pub(super) const MAX_SIGNATURES_PAGE_SIZE: usize = 1000;
+
+/// Successful transaction found by a [`SignaturePager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ConfirmedSignature {
+    pub(super) signature: Signature,
+    pub(super) slot: u64,
+}
+
+/// Pages through the `getSignaturesForAddress` results (newest first) collecting
+/// the successful transactions.
+///
+/// Examines at most `limit` most recent transactions, or the whole history if `None`.
+/// Shared by [`successful_signatures`] and its async counterpart, so that both page
+/// the same way.
+pub(super) struct SignaturePager {
+    limit: Option<usize>,
+    examined: usize,
+    before: Option<Signature>,
+    done: bool,
+    found: Vec<ConfirmedSignature>,
+}
+
+impl SignaturePager {
+    pub(super) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            examined: 0,
+            before: None,
+            // the RPC rejects zero limit
+            done: limit == Some(0),
+            found: Vec::new(),
+        }
+    }
+
+    /// Whether there are no more pages to request.
+    pub(super) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Returns the config of the next page request.
+    pub(super) fn config(&self) -> GetConfirmedSignaturesForAddress2Config {
+        let remaining = self
+            .limit
+            .map_or(usize::MAX, |limit| limit.saturating_sub(self.examined));
+        GetConfirmedSignaturesForAddress2Config {
+            before: self.before,
+            limit: Some(remaining.min(MAX_SIGNATURES_PAGE_SIZE)),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..Default::default()
+        }
+    }
+
+    /// Consumes the next page.
+    ///
+    /// Fails with a custom client error if the page contains an invalid signature.
+    pub(super) fn push(
+        &mut self,
+        page: Vec<RpcConfirmedTransactionStatusWithSignature>,
+    ) -> Result<(), ClientError> {
+        self.examined += page.len();
+        self.done = page.is_empty() || self.limit.is_some_and(|limit| self.examined >= limit);
+        for status in page {
+            let signature = status.signature.parse().map_err(|_| {
+                ClientError::from(ClientErrorKind::Custom(format!(
+                    "Invalid signature {}",
+                    status.signature
+                )))
+            })?;
+            self.before = Some(signature);
+            if status.err.is_none() {
+                self.found.push(ConfirmedSignature {
+                    signature,
+                    slot: status.slot,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the successful transactions found, newest first.
+    pub(super) fn finish(self) -> Vec<ConfirmedSignature> {
+        self.found
+    }
+}
+
+/// Returns successful transactions that touched the address, newest first
+/// (see [`SignaturePager`]).
+pub(super) fn successful_signatures(
+    rpc: &RpcClient,
+    address: &Pubkey,
+    limit: Option<usize>,
+) -> Result<Vec<ConfirmedSignature>, ClientError> {
+    let mut pager = SignaturePager::new(limit);
+    while !pager.is_done() {
+        pager.push(rpc.get_signatures_for_address_with_config(address, pager.config())?)?;
+    }
+    Ok(pager.finish())
+}
+
+/// Lists randomness requested by the given payer, newest first.
+///
+/// The randomness account doesn't record the payer, so this scans the transaction history
+/// of the payer (at most `limit` most recent transactions, or the whole history if `None`)
+/// for `request` instructions. Note that requests made via CPI are not found this way.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let requested = orao_solana_vrf::list_requested_randomness(&program, &program.payer(), Some(100))?;
+/// for (seed, randomness) in requested {
+///     println!("Randomness status: {:?}", randomness.status());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn list_requested_randomness<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    payer: &Pubkey,
+    limit: Option<usize>,
+) -> Result<Vec<([u8; 32], Randomness)>, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let program_id = orao_vrf.id();
+
+    let signatures = successful_signatures(&rpc, payer, limit)?;
+
+    let env = Env::new(program_id);
+    let mut seeds = Vec::new();
+    for ConfirmedSignature { signature, .. } in signatures {
+        let transaction = rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?
+            .transaction
+            .transaction
+            .decode()
+            .ok_or_else(|| {
+                ClientError::from(ClientErrorKind::Custom(format!(
+                    "Transaction {} is not in a binary encoding",
+                    signature
+                )))
+            })?;
+
+        seeds.extend(requested_seeds(&env, payer, &transaction));
+    }
+
+    let mut requested = Vec::with_capacity(seeds.len());
+    for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses = chunk
+            .iter()
+            .map(|seed| randomness_account_address_for(&program_id, seed))
+            .collect::<Vec<_>>();
+        let accounts = rpc.get_multiple_accounts(&addresses)?;
+        for (seed, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                let randomness = Randomness::decode_from_account(&program_id, &account)?;
+                requested.push((*seed, randomness));
+            }
+        }
+    }
+
+    Ok(requested)
+}
+
+/// Returns seeds of the `request` instructions of the transaction paid by the `payer`
+/// (see [`list_requested_randomness`]).
+pub(super) fn requested_seeds(
+    env: &Env,
+    payer: &Pubkey,
+    transaction: &VersionedTransaction,
+) -> Vec<[u8; 32]> {
+    let mut seeds = Vec::new();
+    let keys = transaction.message.static_account_keys();
+    for ix in transaction.message.instructions() {
+        let account = |i: usize| ix.accounts.get(i).and_then(|x| keys.get(*x as usize));
+        if keys.get(ix.program_id_index as usize) != Some(&env.program_id())
+            || !ix
+                .data
+                .starts_with(&crate::instruction::Request::DISCRIMINATOR)
+        {
+            continue;
+        }
+        let Some(seed) = ix
+            .data
+            .get(8..40)
+            .and_then(|x| <[u8; 32]>::try_from(x).ok())
+        else {
+            continue;
+        };
+        // see `crate::Request` for the accounts order
+        if account(0) == Some(payer) && account(3) == Some(&env.randomness_account_address(&seed)) {
+            seeds.push(seed);
+        }
+    }
+    seeds
+}
+
+/// Fulfill transaction of a randomness request (see [`fulfillment_history`]).
+#[derive(Debug, Clone)]
+pub struct FulfillmentRecord {
+    /// Fulfill transaction signature.
+    pub signature: Signature,
+    /// Slot of the fulfill transaction.
+    pub slot: u64,
+    /// Block time of the fulfill transaction, if available.
+    pub block_time: Option<i64>,
+    /// Valid responses carried by the transaction.
+    pub responses: Vec<SignedMessage>,
+}
+
+/// Returns successful fulfill transactions of the request for the given seed, newest first.
+///
+/// Examines at most `limit` most recent transactions of the randomness account
+/// (or the whole history if `None`). Gives the full fulfillment timeline of the request
+/// rather than just the current account state.
+pub fn fulfillment_history<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    limit: Option<usize>,
+) -> Result<Vec<FulfillmentRecord>, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let scan = limit.map_or(FulfillmentScan::Exhaustive, FulfillmentScan::Latest);
+
+    let mut history = Vec::new();
+    for signature in randomness_signatures(&rpc, &orao_vrf.id(), seed, scan)? {
+        let confirmed = rpc.get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let transaction = confirmed.transaction.transaction.decode().ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(format!(
+                "Transaction {} is not in a binary encoding",
+                signature
+            )))
+        })?;
+        let responses = fulfill_responses(&orao_vrf.id(), &transaction, seed)?;
+        if !responses.is_empty() {
+            history.push(FulfillmentRecord {
+                signature,
+                slot: confirmed.slot,
+                block_time: confirmed.block_time,
+                responses,
+            });
+        }
+    }
+
+    Ok(history)
+}
+
+/// Request-to-fulfill timing of a randomness request (see [`request_latency`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLatency {
+    /// Slot of the request transaction, i.e. the slot the randomness account was created at.
+    pub request_slot: u64,
+    /// Slot of the fulfill transaction that completed the randomness.
+    ///
+    /// `None` if the randomness is not yet fulfilled.
+    pub fulfillment_slot: Option<u64>,
+}
+
+impl RequestLatency {
+    /// Returns the number of slots between the request and its fulfillment.
+    pub fn slots(&self) -> Option<u64> {
+        self.fulfillment_slot
+            .map(|slot| slot.saturating_sub(self.request_slot))
+    }
+}
+
+/// Returns slots of the request and the fulfillment of the randomness for the given seed.
+///
+/// The randomness account doesn't record any slots, so both are looked up
+/// in the transaction history of the account (the whole history is scanned).
+/// Fails with [`anchor_client::ClientError::AccountNotFound`] if there is no request.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let latency = orao_solana_vrf::request_latency(&program, &seed)?;
+/// println!("Fulfilled in {:?} slots", latency.slots());
+/// # Ok(()) }
+/// ```
+pub fn request_latency<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+) -> Result<RequestLatency, anchor_client::ClientError> {
+    let randomness = get_randomness(orao_vrf, seed)?;
+    let address = randomness_account_address_for(&orao_vrf.id(), seed);
+    let request_slot = request_transaction(&orao_vrf.rpc(), &address)?
+        .ok_or(anchor_client::ClientError::AccountNotFound)?
+        .slot;
+
+    // responses are stored in the order of arrival, so the last one completed the randomness
+    let fulfillment_slot = match (randomness.fulfilled(), randomness.responses.last()) {
+        (Some(_), Some(last)) => fulfillment_history(orao_vrf, seed, None)?
+            .into_iter()
+            .find(|record| {
+                record
+                    .responses
+                    .iter()
+                    .any(|response| response.pubkey == last.pubkey)
+            })
+            .map(|record| record.slot),
+        _ => None,
+    };
+
+    Ok(RequestLatency {
+        request_slot,
+        fulfillment_slot,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_sdk::transaction::TransactionError;
+
+    use super::*;
+
+    fn status(i: u8, err: Option<TransactionError>) -> RpcConfirmedTransactionStatusWithSignature {
+        RpcConfirmedTransactionStatusWithSignature {
+            signature: Signature::from([i; 64]).to_string(),
+            slot: i.into(),
+            err,
+            memo: None,
+            block_time: None,
+            confirmation_status: None,
+        }
+    }
+
+    #[test]
+    fn zero_limit_requests_nothing() {
+        assert!(SignaturePager::new(Some(0)).is_done());
+    }
+
+    #[test]
+    fn pages_are_capped_until_the_limit() {
+        let mut pager = SignaturePager::new(Some(1500));
+        assert_eq!(pager.config().limit, Some(MAX_SIGNATURES_PAGE_SIZE));
+        assert_eq!(pager.config().before, None);
+
+        pager
+            .push(vec![
+                status(1, None),
+                status(2, Some(TransactionError::AccountInUse)),
+            ])
+            .unwrap();
+        assert!(!pager.is_done());
+        assert_eq!(pager.config().limit, Some(MAX_SIGNATURES_PAGE_SIZE));
+        assert_eq!(pager.config().before, Some(Signature::from([2; 64])));
+
+        pager.push(vec![status(3, None); 1000]).unwrap();
+        assert!(!pager.is_done());
+        assert_eq!(pager.config().limit, Some(498));
+
+        pager.push(vec![status(4, None); 498]).unwrap();
+        assert!(pager.is_done());
+        let found = pager.finish();
+        assert_eq!(found.len(), 1499);
+        assert_eq!(found[0].signature, Signature::from([1; 64]));
+        assert_eq!(found[1].slot, 3);
+    }
+
+    #[test]
+    fn unlimited_pager_stops_on_empty_page() {
+        let mut pager = SignaturePager::new(None);
+        assert_eq!(pager.config().limit, Some(MAX_SIGNATURES_PAGE_SIZE));
+        pager.push(vec![status(1, None)]).unwrap();
+        assert!(!pager.is_done());
+        pager.push(vec![]).unwrap();
+        assert!(pager.is_done());
+        assert_eq!(pager.finish().len(), 1);
+    }
+
+    #[test]
+    fn invalid_signature_fails_the_scan() {
+        let mut pager = SignaturePager::new(None);
+        let mut invalid = status(1, None);
+        invalid.signature = "invalid".to_string();
+        assert!(pager.push(vec![invalid]).is_err());
+    }
+}
@@ -0,0 +1,1226 @@
+//! Async API based on the nonblocking RPC client.
+//!
+//! **Note:** requires `async` feature to be enabled.
+
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+        rpc_config::{RpcAccountInfoConfig, RpcTransactionConfig},
+        rpc_request::MAX_MULTIPLE_ACCOUNTS,
+        rpc_response::Response as RpcResponse,
+    },
+    solana_sdk::{
+        account::Account, commitment_config::CommitmentConfig, hash::Hash,
+        instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE, signature::Signature,
+        signer::Signer, transaction::Transaction,
+    },
+};
+use anchor_lang::prelude::Pubkey;
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::state::{NetworkState, Randomness, RandomnessStatus};
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use super::{
+    batch::{pack_requests, RequestGroup},
+    history::{requested_seeds, ConfirmedSignature, SignaturePager},
+    priority::fee_percentile,
+    request_fee_accounts,
+    retry::is_transient,
+    verify_fulfillment_with, Env, FulfillmentScan, FulfillmentWaiter, RequestBuilder, RetryPolicy,
+    SignedMessage, EMPTY_DATA_RETRIES, EMPTY_DATA_RETRY_DELAY,
+};
+
+/// Async client of a VRF deployment.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::solana_client::nonblocking::{
+///     pubsub_client::PubsubClient, rpc_client::RpcClient,
+/// };
+/// use futures::StreamExt;
+/// use orao_solana_vrf::nonblocking::VrfClient;
+///
+/// # let payer: anchor_client::solana_sdk::signer::keypair::Keypair = panic!();
+/// let client = VrfClient::new(RpcClient::new("https://api.devnet.solana.com".into()));
+/// let pubsub = PubsubClient::new("wss://api.devnet.solana.com").await?;
+///
+/// let seeds = vec![rand::random(), rand::random()];
+/// let mut fulfilled = client.request_and_stream(&pubsub, &payer, &seeds).await?;
+/// while let Some((_seed, randomness)) = fulfilled.next().await {
+///     println!("Randomness fulfilled: {:?}", randomness.fulfilled());
+/// }
+/// # Ok(()) }
+/// ```
+pub struct VrfClient {
+    rpc: Arc<RpcClient>,
+    rpc_calls: Arc<RpcCallCounter>,
+    env: Env,
+    commitment: Option<CommitmentConfig>,
+    retry_policy: RetryPolicy,
+}
+
+impl VrfClient {
+    /// Creates a client of the VRF deployed at [`crate::id`].
+    pub fn new(rpc: RpcClient) -> Self {
+        Self {
+            rpc: Arc::new(rpc),
+            rpc_calls: Default::default(),
+            env: Env::default(),
+            commitment: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Change the VRF program id.
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.env = self.env.with_program_id(program_id);
+        self
+    }
+
+    /// Change the VRF deployment environment, e.g. to use a fork with different seeds.
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+    /// use orao_solana_vrf::{nonblocking::VrfClient, Env};
+    ///
+    /// # let program_id: anchor_client::solana_sdk::pubkey::Pubkey = panic!();
+    /// // e.g. a program deployed to `solana-test-validator`
+    /// let env = Env::new(program_id)
+    ///     .with_config_account_seed(b"my-vrf-network-configuration".to_vec())?
+    ///     .with_randomness_account_seed(b"my-vrf-randomness-request".to_vec())?;
+    /// let client = VrfClient::new(RpcClient::new("http://localhost:8899".into())).with_env(env);
+    /// client.verify_program_deployed().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn with_env(mut self, env: Env) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Change the commitment level of account reads (defaults to the RPC client commitment).
+    ///
+    /// Request transactions are always confirmed at the RPC client commitment, so e.g.
+    /// a client created with [`RpcClient::new_with_commitment`] at `finalized` and
+    /// reading at `confirmed` gets safe submission along with low-latency polling.
+    /// Transaction history lookups of the verification helpers always use `confirmed`.
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    /// Returns the commitment level of account reads (see [`VrfClient::with_commitment`]).
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment.unwrap_or_else(|| self.rpc.commitment())
+    }
+
+    /// Returns the VRF deployment environment.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Logs a warning once the number of RPC calls made by this client crosses
+    /// the `threshold` (see [`VrfClient::rpc_calls`]).
+    ///
+    /// Useful with metered or rate-limited RPC endpoints.
+    pub fn with_rpc_call_warning(mut self, threshold: usize) -> Self {
+        self.rpc_calls = Arc::new(RpcCallCounter {
+            warning_threshold: Some(threshold),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Retry transient RPC failures, e.g. rate limiting of public RPC nodes
+    /// (see [`RetryPolicy::is_retryable`]). Failures are not retried by default.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Returns the number of RPC calls made by this client so far.
+    ///
+    /// Every RPC method invocation counts as one call (e.g. `send_and_confirm_transaction`
+    /// counts as one, regardless of the confirmation polling it does), and so does every retry.
+    pub fn rpc_calls(&self) -> usize {
+        self.rpc_calls.calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the RPC client.
+    ///
+    /// Calls made directly through it are not counted by [`VrfClient::rpc_calls`].
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// Returns the VRF program id.
+    pub fn program_id(&self) -> Pubkey {
+        self.env.program_id()
+    }
+
+    /// Returns randomness account address for the given seed
+    /// (see [`Env::randomness_account_address`]).
+    pub fn randomness_address(&self, seed: &[u8; 32]) -> Pubkey {
+        self.env.randomness_account_address(seed)
+    }
+
+    /// Checks that the VRF program is deployed (see [`crate::verify_program_deployed`]).
+    ///
+    /// Useful right after pointing the client at a custom deployment
+    /// (see [`VrfClient::with_env`]).
+    pub async fn verify_program_deployed(&self) -> Result<(), anchor_client::ClientError> {
+        match self.account(&self.env.program_id()).await {
+            Ok(account) if account.executable => Ok(()),
+            Ok(_) | Err(anchor_client::ClientError::AccountNotFound) => {
+                Err(crate::SdkError::ProgramNotDeployed.into())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetches VRF on-chain state (see [`crate::get_network_state`]).
+    pub async fn get_network_state(&self) -> Result<NetworkState, anchor_client::ClientError> {
+        let address = self.env.network_state_account_address();
+        let account = self.account(&address).await?;
+        self.env.decode_network_state(&account)
+    }
+
+    /// Fetches randomness request state for the given seed (see [`crate::get_randomness`]).
+    pub async fn get_randomness(
+        &self,
+        seed: &[u8; 32],
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        let address = self.env.randomness_account_address(seed);
+        let mut retries = 0;
+        loop {
+            let account = self.account(&address).await?;
+            // a lagging RPC node might return a just created account without the data
+            if account.data.is_empty() && retries < EMPTY_DATA_RETRIES {
+                tokio::time::sleep(EMPTY_DATA_RETRY_DELAY).await;
+                retries += 1;
+                continue;
+            }
+            return self.env.decode_randomness(&account);
+        }
+    }
+
+    /// Fetches randomness request status for the given seed
+    /// (see [`crate::get_randomness_status`]).
+    pub async fn get_randomness_status(
+        &self,
+        seed: &[u8; 32],
+    ) -> Result<RandomnessStatus, anchor_client::ClientError> {
+        match self.get_randomness(seed).await {
+            Ok(randomness) => Ok(randomness.status()),
+            Err(anchor_client::ClientError::AccountNotFound) => Ok(RandomnessStatus::NotRequested),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetches randomness request state for many seeds (see [`crate::get_randomness_batch`]).
+    pub async fn get_randomness_batch(
+        &self,
+        seeds: &[[u8; 32]],
+    ) -> Result<Vec<Result<Randomness, anchor_client::ClientError>>, anchor_client::ClientError>
+    {
+        let mut batch = Vec::with_capacity(seeds.len());
+        for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses = chunk
+                .iter()
+                .map(|seed| self.env.randomness_account_address(seed))
+                .collect::<Vec<_>>();
+            let accounts = self
+                .retry(|| {
+                    self.rpc
+                        .get_multiple_accounts_with_commitment(&addresses, self.commitment())
+                })
+                .await?
+                .value;
+            batch.extend(accounts.into_iter().map(|account| {
+                let account = account.ok_or(anchor_client::ClientError::AccountNotFound)?;
+                self.env.decode_randomness(&account)
+            }));
+        }
+        Ok(batch)
+    }
+
+    /// Lists randomness requested by the given payer, newest first
+    /// (see [`crate::list_requested_randomness`]).
+    pub async fn list_requested_randomness(
+        &self,
+        payer: &Pubkey,
+        limit: Option<usize>,
+    ) -> Result<Vec<([u8; 32], Randomness)>, anchor_client::ClientError> {
+        let signatures = self.successful_signatures(payer, limit).await?;
+
+        let mut seeds = Vec::new();
+        for ConfirmedSignature { signature, .. } in signatures {
+            let transaction = self
+                .retry(|| {
+                    self.rpc.get_transaction_with_config(
+                        &signature,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Base64),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                })
+                .await?
+                .transaction
+                .transaction
+                .decode()
+                .ok_or_else(|| {
+                    ClientError::from(ClientErrorKind::Custom(format!(
+                        "Transaction {} is not in a binary encoding",
+                        signature
+                    )))
+                })?;
+            seeds.extend(requested_seeds(&self.env, payer, &transaction));
+        }
+
+        let batch = self.get_randomness_batch(&seeds).await?;
+        let mut requested = Vec::with_capacity(seeds.len());
+        for (seed, randomness) in seeds.into_iter().zip(batch) {
+            match randomness {
+                Ok(randomness) => requested.push((seed, randomness)),
+                Err(anchor_client::ClientError::AccountNotFound) => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(requested)
+    }
+
+    /// Requests randomness (see [`crate::RequestBuilder`]).
+    ///
+    /// Returns the signature of the request transaction. Fails with
+    /// [`crate::Error::SeedAlreadyInUse`] if the seed is already requested
+    /// (see [`crate::request_idempotent`] to get the signature of the existing request).
+    ///
+    /// The `payer` could be any [`Signer`] implementation, e.g. a `Keypair`,
+    /// a `Presigner` or a remote signer wrapper.
+    pub async fn request_randomness(
+        &self,
+        payer: &dyn Signer,
+        request: &RequestBuilder,
+    ) -> Result<Signature, anchor_client::ClientError> {
+        self.request_randomness_with_payer(payer, payer, request)
+            .await
+    }
+
+    /// Requests randomness on behalf of the `requester` while the `fee_payer` pays
+    /// the transaction fees (see [`VrfClient::request_randomness`]).
+    ///
+    /// The `requester` is the payer of the `request` instruction, so it still pays
+    /// the request fee and the randomness account rent. Both sign the transaction.
+    pub async fn request_randomness_with_payer(
+        &self,
+        fee_payer: &dyn Signer,
+        requester: &dyn Signer,
+        request: &RequestBuilder,
+    ) -> Result<Signature, anchor_client::ClientError> {
+        let transaction = self
+            .request_with_payer_transaction(fee_payer, requester, request)
+            .await?;
+        self.send(&transaction).await
+    }
+
+    /// Simulates the request transaction without submitting it
+    /// (see [`VrfClient::request_randomness`]).
+    ///
+    /// Validates the request cheaply before spending fees, e.g. catches an insufficient
+    /// payer balance. Fails with [`crate::SdkError::SimulationFailed`] with the transaction
+    /// error and the program logs in the error message if the simulation fails.
+    pub async fn simulate_request_randomness(
+        &self,
+        payer: &dyn Signer,
+        request: &RequestBuilder,
+    ) -> Result<RequestSimulation, anchor_client::ClientError> {
+        let transaction = self
+            .request_with_payer_transaction(payer, payer, request)
+            .await?;
+        let simulation = self
+            .retry(|| self.rpc.simulate_transaction(&transaction))
+            .await?
+            .value;
+        let logs = simulation.logs.unwrap_or_default();
+        if let Some(err) = simulation.err {
+            return Err(failure(crate::SdkError::SimulationFailed, err, &logs));
+        }
+        Ok(RequestSimulation {
+            units_consumed: simulation.units_consumed,
+            logs,
+        })
+    }
+
+    /// Builds the request transaction without signing it, for external signing
+    /// (e.g. a hardware wallet or an air-gapped machine).
+    ///
+    /// The `requester` pays both the request fee and the transaction fees.
+    /// Sign the transaction message (see [`Transaction::message_data`]) before
+    /// the recent blockhash expires and submit it with [`VrfClient::submit_signed`].
+    pub async fn build_unsigned_request(
+        &self,
+        requester: &Pubkey,
+        request: &RequestBuilder,
+    ) -> Result<Transaction, anchor_client::ClientError> {
+        let message = self.request_message(requester, requester, request).await?;
+        Ok(Transaction::new_unsigned(message))
+    }
+
+    /// Submits an externally signed transaction (see [`VrfClient::build_unsigned_request`]).
+    ///
+    /// Fails with a custom client error if the transaction is not fully signed.
+    pub async fn submit_signed(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Signature, anchor_client::ClientError> {
+        if !transaction.is_signed() {
+            return Err(ClientError::from(ClientErrorKind::Custom(
+                "Transaction is not fully signed".to_string(),
+            ))
+            .into());
+        }
+        self.send(transaction).await
+    }
+
+    /// Builds the signed request transaction (see [`VrfClient::request_randomness_with_payer`]).
+    async fn request_with_payer_transaction(
+        &self,
+        fee_payer: &dyn Signer,
+        requester: &dyn Signer,
+        request: &RequestBuilder,
+    ) -> Result<Transaction, anchor_client::ClientError> {
+        let message = self
+            .request_message(&fee_payer.pubkey(), &requester.pubkey(), request)
+            .await?;
+
+        let mut signers = vec![fee_payer];
+        if requester.pubkey() != fee_payer.pubkey() {
+            signers.push(requester);
+        }
+
+        let blockhash = message.recent_blockhash;
+        Ok(Transaction::new(&signers, message, blockhash))
+    }
+
+    /// Builds the request transaction message with a recent blockhash.
+    ///
+    /// Fails with [`crate::Error::SeedAlreadyInUse`] if the seed is already requested.
+    async fn request_message(
+        &self,
+        fee_payer: &Pubkey,
+        requester: &Pubkey,
+        request: &RequestBuilder,
+    ) -> Result<Message, anchor_client::ClientError> {
+        let address = self.env.randomness_account_address(&request.seed);
+        match self.account(&address).await {
+            Ok(_) => return Err(crate::Error::SeedAlreadyInUse.into()),
+            Err(anchor_client::ClientError::AccountNotFound) => (),
+            Err(err) => return Err(err),
+        }
+
+        let network_state = self.get_network_state().await?;
+        let (treasury, remaining_accounts) =
+            request_fee_accounts(&network_state.config, request.token_wallet)?;
+
+        let instructions =
+            request.instructions(&self.env, *requester, treasury, &remaining_accounts);
+
+        let blockhash = self.retry(|| self.rpc.get_latest_blockhash()).await?;
+        Ok(Message::new_with_blockhash(
+            &instructions,
+            Some(fee_payer),
+            &blockhash,
+        ))
+    }
+
+    /// Requests randomness and waits for the fulfillment (see [`crate::FulfillmentWaiter`]).
+    ///
+    /// The returned future is lazy – nothing is sent until it is polled, so callers
+    /// could build many of them up front and control the submission order and rate:
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::{stream, StreamExt};
+    /// use orao_solana_vrf::{nonblocking::VrfClient, FulfillmentWaiter, RequestBuilder};
+    ///
+    /// # let client: VrfClient = panic!();
+    /// # let payer: anchor_client::solana_sdk::signer::keypair::Keypair = panic!();
+    /// # let seeds: Vec<[u8; 32]> = panic!();
+    /// let pending = seeds.iter().map(|seed| {
+    ///     client.request_and_wait(&payer, RequestBuilder::new(*seed), FulfillmentWaiter::new(*seed))
+    /// });
+    /// // at most two requests in flight at a time, submitted in the given order
+    /// let fulfilled = stream::iter(pending).buffered(2).collect::<Vec<_>>().await;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// A seed that is already requested is not requested again – its fulfillment is awaited.
+    pub async fn request_and_wait(
+        &self,
+        payer: &dyn Signer,
+        request: RequestBuilder,
+        waiter: FulfillmentWaiter,
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        self.request_randomness_if_new(payer, &request).await?;
+        waiter.wait_async(self).await
+    }
+
+    /// Requests randomness paying priority fee at the given percentile (e.g. `75`)
+    /// of the recent fees paid for the accounts of the request
+    /// (see [`crate::recent_fee_percentile`]).
+    ///
+    /// The fee is computed right before sending, so it adapts to the current congestion.
+    /// It overrides the compute unit price of the `request`.
+    pub async fn request_randomness_auto_fee(
+        &self,
+        payer: &dyn Signer,
+        request: &RequestBuilder,
+        percentile: u8,
+    ) -> Result<Signature, anchor_client::ClientError> {
+        let accounts = [
+            payer.pubkey(),
+            self.env.network_state_account_address(),
+            self.env.randomness_account_address(&request.seed),
+        ];
+        let fees = self
+            .retry(|| self.rpc.get_recent_prioritization_fees(&accounts))
+            .await?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        let compute_unit_price = fee_percentile(fees, percentile);
+
+        let mut request = request.clone();
+        request.compute_unit_price = (compute_unit_price > 0).then_some(compute_unit_price);
+        self.request_randomness(payer, &request).await
+    }
+
+    /// Requests randomness unless the seed is already requested
+    /// (see [`VrfClient::request_randomness`]).
+    ///
+    /// Returns `None` if the seed is already in use, so callers could tell whether
+    /// a new request went on-chain. Use it where seed reuse is expected – otherwise
+    /// prefer [`VrfClient::request_randomness`] that fails on reuse.
+    pub async fn request_randomness_if_new(
+        &self,
+        payer: &dyn Signer,
+        request: &RequestBuilder,
+    ) -> Result<Option<Signature>, anchor_client::ClientError> {
+        match self.request_randomness(payer, request).await {
+            Ok(signature) => Ok(Some(signature)),
+            Err(err) if crate::Error::SeedAlreadyInUse.matches(&err) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Requests randomness for the given seeds (see [`crate::BatchRequestBuilder`]).
+    ///
+    /// Requests are packed into transactions the same way [`crate::BatchRequestBuilder`]
+    /// does. Fees are paid with SPL token from the `token_wallet` if given
+    /// (see [`crate::BatchRequestBuilder::pay_with_token`]), or with lamports otherwise.
+    ///
+    /// Seeds already in use are skipped. Returns signatures of the request transactions.
+    pub async fn request_batch(
+        &self,
+        payer: &dyn Signer,
+        seeds: &[[u8; 32]],
+        token_wallet: Option<Pubkey>,
+    ) -> Result<Vec<Signature>, anchor_client::ClientError> {
+        let groups = self.pack_batch(payer, seeds, token_wallet).await?;
+
+        let mut signatures = Vec::new();
+        for (_, instructions) in groups {
+            let blockhash = self.retry(|| self.rpc.get_latest_blockhash()).await?;
+            let transaction = self.request_transaction(payer, &instructions, blockhash);
+            signatures.push(self.send(&transaction).await?);
+        }
+
+        Ok(signatures)
+    }
+
+    /// Requests randomness for the given seeds submitting transactions concurrently
+    /// (see [`VrfClient::request_batch`]).
+    ///
+    /// Every transaction uses the same blockhash (fetched once), and at most `concurrency`
+    /// transactions are in flight at a time. Seeds already in use are skipped.
+    /// Returns the outcome for every request transaction – a failed transaction doesn't
+    /// affect the others.
+    ///
+    /// Note that all the transactions expire along with the blockhash (in about a minute),
+    /// so keep the batch small enough to get submitted in time.
+    pub async fn request_batch_concurrent(
+        &self,
+        payer: &dyn Signer,
+        seeds: &[[u8; 32]],
+        token_wallet: Option<Pubkey>,
+        concurrency: usize,
+    ) -> Result<Vec<Result<Signature, anchor_client::ClientError>>, anchor_client::ClientError>
+    {
+        let groups = self.pack_batch(payer, seeds, token_wallet).await?;
+
+        let blockhash = self.retry(|| self.rpc.get_latest_blockhash()).await?;
+        let transactions = groups
+            .iter()
+            .map(|(_, instructions)| self.request_transaction(payer, instructions, blockhash))
+            .collect::<Vec<_>>();
+
+        Ok(stream::iter(transactions.iter())
+            .map(|transaction| self.send(transaction))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await)
+    }
+
+    /// Streams randomness of the given seeds as it gets fulfilled.
+    ///
+    /// Subscribes to every randomness account via the `pubsub` client, so the stream
+    /// yields every seed exactly once, as soon as the fulfillment is observed –
+    /// already fulfilled seeds are yielded first.
+    pub async fn stream_fulfillments<'a>(
+        &self,
+        pubsub: &'a PubsubClient,
+        seeds: &[[u8; 32]],
+    ) -> Result<impl Stream<Item = ([u8; 32], Randomness)> + 'a, anchor_client::ClientError> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(self.commitment()),
+            ..Default::default()
+        };
+
+        // subscribe before looking at the current state so that no update is missed
+        let mut subscriptions = Vec::with_capacity(seeds.len());
+        for seed in seeds {
+            let address = self.env.randomness_account_address(seed);
+            let (updates, _) = pubsub
+                .account_subscribe(&address, Some(config.clone()))
+                .await?;
+            subscriptions.push((*seed, updates));
+        }
+
+        let mut current = Vec::with_capacity(seeds.len());
+        for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses = chunk
+                .iter()
+                .map(|seed| self.env.randomness_account_address(seed))
+                .collect::<Vec<_>>();
+            let accounts = self
+                .retry(|| {
+                    self.rpc
+                        .get_multiple_accounts_with_commitment(&addresses, self.commitment())
+                })
+                .await?
+                .value;
+            current.extend(
+                accounts
+                    .into_iter()
+                    .map(|account| fulfilled_randomness(&self.env, account.as_ref()?)),
+            );
+        }
+
+        let pending =
+            subscriptions
+                .into_iter()
+                .zip(current)
+                .map(|((seed, updates), randomness)| match randomness {
+                    Some(randomness) => stream::once(async move { (seed, randomness) }).boxed(),
+                    None => self.fulfillment_updates(seed, updates),
+                });
+
+        Ok(stream::select_all(pending))
+    }
+
+    /// Requests randomness for the given seeds and streams it as it gets fulfilled
+    /// (see [`VrfClient::request_batch`] and [`VrfClient::stream_fulfillments`]).
+    pub async fn request_and_stream<'a>(
+        &self,
+        pubsub: &'a PubsubClient,
+        payer: &dyn Signer,
+        seeds: &[[u8; 32]],
+    ) -> Result<impl Stream<Item = ([u8; 32], Randomness)> + 'a, anchor_client::ClientError> {
+        let fulfillments = self.stream_fulfillments(pubsub, seeds).await?;
+        self.request_batch(payer, seeds, None).await?;
+        Ok(fulfillments)
+    }
+
+    /// Performs offchain verification of the randomness against the effective list
+    /// of fulfillment authorities (see [`Randomness::verify_offchain`]).
+    ///
+    /// Fails with [`crate::SdkError::RandomnessNotFulfilled`] if the randomness
+    /// is not yet fulfilled.
+    pub async fn verify_randomness_offchain(
+        &self,
+        seed: &[u8; 32],
+    ) -> Result<bool, anchor_client::ClientError> {
+        let network_state = self.get_network_state().await?;
+        let randomness = self.get_randomness(seed).await?;
+        randomness.check_seed(seed)?;
+        randomness.check_fulfilled()?;
+        Ok(randomness.verify_offchain(&network_state.config.fulfillment_authorities))
+    }
+
+    /// Verifies randomness against its fulfill transactions (see [`crate::verify_fulfillment`]).
+    pub async fn verify_fulfillment(
+        &self,
+        seed: &[u8; 32],
+        scan: FulfillmentScan,
+    ) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+        let randomness = self.get_randomness(seed).await?;
+        randomness.check_seed(seed)?;
+        let address = self.env.randomness_account_address(seed);
+
+        let signatures = self
+            .successful_signatures(&address, scan.limit())
+            .await?
+            .into_iter()
+            .map(|x| x.signature)
+            .collect();
+
+        self.verify_signatures_of(&randomness, signatures).await
+    }
+
+    /// Verifies randomness against the given fulfill transactions, skipping the scan
+    /// of the randomness account history (see [`crate::verify_with_signatures`]).
+    pub async fn verify_with_signatures(
+        &self,
+        seed: &[u8; 32],
+        signatures: &[Signature],
+    ) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+        let randomness = self.get_randomness(seed).await?;
+        randomness.check_seed(seed)?;
+        self.verify_signatures_of(&randomness, signatures.to_vec())
+            .await
+    }
+
+    /// Verifies randomness of many seeds against their fulfill transactions
+    /// (see [`VrfClient::verify_fulfillment`]).
+    ///
+    /// At most `concurrency` seeds are verified at a time, so that large batches
+    /// don't overwhelm the RPC. Returns the outcome for every seed in the given order.
+    pub async fn verify_batch(
+        &self,
+        seeds: &[[u8; 32]],
+        scan: FulfillmentScan,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<SignedMessage>, anchor_client::ClientError>> {
+        stream::iter(seeds)
+            .map(|seed| self.verify_fulfillment(seed, scan))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Prefetches the given transactions and verifies the randomness against them.
+    async fn verify_signatures_of(
+        &self,
+        randomness: &Randomness,
+        signatures: Vec<Signature>,
+    ) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+        let mut transactions = HashMap::with_capacity(signatures.len());
+        for signature in signatures.iter() {
+            let transaction = self
+                .retry(|| {
+                    self.rpc.get_transaction_with_config(
+                        signature,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Base64),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                })
+                .await?;
+            transactions.insert(*signature, transaction.transaction.transaction);
+        }
+
+        // every transaction is prefetched, so the lookup never fails
+        verify_fulfillment_with(
+            &self.env.program_id(),
+            randomness,
+            signatures,
+            |signature| {
+                transactions
+                    .remove(signature)
+                    .ok_or(anchor_client::ClientError::AccountNotFound)
+            },
+        )
+    }
+
+    /// Turns account notifications into the fulfilled randomness (yielded once).
+    fn fulfillment_updates<'a>(
+        &self,
+        seed: [u8; 32],
+        updates: BoxStream<'a, RpcResponse<UiAccount>>,
+    ) -> BoxStream<'a, ([u8; 32], Randomness)> {
+        let rpc = self.rpc.clone();
+        let rpc_calls = self.rpc_calls.clone();
+        let env = self.env.clone();
+        let address = self.env.randomness_account_address(&seed);
+        let commitment = self.commitment();
+        updates
+            .filter_map(move |update| {
+                let rpc = rpc.clone();
+                let rpc_calls = rpc_calls.clone();
+                let env = env.clone();
+                async move {
+                    let account = match update.value.decode::<Account>() {
+                        Some(account) => account,
+                        // the notification is incomplete (e.g. lacks a valid owner),
+                        // so fetch the whole account instead
+                        None => {
+                            rpc_calls.record();
+                            let response =
+                                rpc.get_account_with_commitment(&address, commitment).await;
+                            response.ok()?.value?
+                        }
+                    };
+                    Some((seed, fulfilled_randomness(&env, &account)?))
+                }
+            })
+            .take(1)
+            .boxed()
+    }
+
+    /// Returns the given seeds that are not yet in use (duplicates removed).
+    async fn unrequested(
+        &self,
+        seeds: &[[u8; 32]],
+    ) -> Result<Vec<[u8; 32]>, anchor_client::ClientError> {
+        let mut seeds = seeds.to_vec();
+        let mut seen = std::collections::HashSet::new();
+        seeds.retain(|seed| seen.insert(*seed));
+
+        let mut to_request = Vec::with_capacity(seeds.len());
+        for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses = chunk
+                .iter()
+                .map(|seed| self.env.randomness_account_address(seed))
+                .collect::<Vec<_>>();
+            let accounts = self
+                .retry(|| {
+                    self.rpc
+                        .get_multiple_accounts_with_commitment(&addresses, self.commitment())
+                })
+                .await?
+                .value;
+            for (seed, account) in chunk.iter().zip(accounts) {
+                if account.is_none() {
+                    to_request.push(*seed);
+                }
+            }
+        }
+        Ok(to_request)
+    }
+
+    /// Packs `request` instructions of the seeds not yet in use into transactions
+    /// (see [`crate::BatchRequestBuilder`]).
+    async fn pack_batch(
+        &self,
+        payer: &dyn Signer,
+        seeds: &[[u8; 32]],
+        token_wallet: Option<Pubkey>,
+    ) -> Result<Vec<RequestGroup>, anchor_client::ClientError> {
+        let network_state = self.get_network_state().await?;
+        let (treasury, remaining_accounts) =
+            request_fee_accounts(&network_state.config, token_wallet)?;
+        let to_request = self.unrequested(seeds).await?;
+        pack_requests(
+            &self.env,
+            payer.pubkey(),
+            to_request,
+            treasury,
+            &remaining_accounts,
+            PACKET_DATA_SIZE,
+        )
+    }
+
+    /// Builds a signed transaction of the given instructions.
+    fn request_transaction(
+        &self,
+        payer: &dyn Signer,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> Transaction {
+        Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], blockhash)
+    }
+
+    /// Sends and confirms the transaction.
+    ///
+    /// If the transaction is confirmed but failed on-chain, then this fails with
+    /// [`crate::SdkError::RequestTransactionFailed`] with the transaction error
+    /// and the program logs in the error message. Transactions rejected by the preflight
+    /// simulation fail with the RPC error as-is (it already carries the logs).
+    async fn send(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Signature, anchor_client::ClientError> {
+        log::trace!("VRF request transaction: {:?}", transaction);
+        if let Some(signature) = transaction.signatures.first() {
+            log::debug!("Sending VRF request transaction {}", signature);
+        }
+        let err = match self
+            .retry(|| self.rpc.send_and_confirm_transaction(transaction))
+            .await
+        {
+            Ok(signature) => {
+                log::info!("VRF request transaction {} confirmed", signature);
+                return Ok(signature);
+            }
+            Err(err) => err,
+        };
+        log::debug!("VRF request transaction failed: {}", err);
+        let ClientErrorKind::TransactionError(transaction_error) = err.kind() else {
+            return Err(err.into());
+        };
+
+        let logs = match transaction.signatures.first() {
+            Some(signature) => self.transaction_logs(signature).await,
+            None => Vec::new(),
+        };
+        Err(failure(
+            crate::SdkError::RequestTransactionFailed,
+            transaction_error,
+            &logs,
+        ))
+    }
+
+    /// Returns program logs of the confirmed transaction, or nothing if unavailable.
+    async fn transaction_logs(&self, signature: &Signature) -> Vec<String> {
+        let transaction = self
+            .retry(|| {
+                self.rpc.get_transaction_with_config(
+                    signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+            })
+            .await;
+        transaction
+            .ok()
+            .and_then(|transaction| transaction.transaction.meta)
+            .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages))
+            .unwrap_or_default()
+    }
+
+    /// Makes an RPC call, retrying transient failures (see [`VrfClient::with_retry_policy`]).
+    ///
+    /// Every attempt is recorded as an RPC call.
+    async fn retry<T, F, Fut>(&self, mut call: F) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ClientError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            self.rpc_calls.record();
+            match call().await {
+                Err(err) if attempt < self.retry_policy.max_retries && is_transient(&err) => {
+                    tokio::time::sleep(self.retry_policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns successful transactions that touched the address, newest first
+    /// (see [`SignaturePager`]).
+    async fn successful_signatures(
+        &self,
+        address: &Pubkey,
+        limit: Option<usize>,
+    ) -> Result<Vec<ConfirmedSignature>, ClientError> {
+        let mut pager = SignaturePager::new(limit);
+        while !pager.is_done() {
+            let page = self
+                .retry(|| {
+                    self.rpc
+                        .get_signatures_for_address_with_config(address, pager.config())
+                })
+                .await?;
+            pager.push(page)?;
+        }
+        Ok(pager.finish())
+    }
+
+    async fn account(&self, address: &Pubkey) -> Result<Account, anchor_client::ClientError> {
+        self.retry(|| {
+            self.rpc
+                .get_account_with_commitment(address, self.commitment())
+        })
+        .await?
+        .value
+        .ok_or(anchor_client::ClientError::AccountNotFound)
+    }
+}
+
+/// Adapter exposing the blocking API to async code.
+///
+/// Blocking helpers must not run on async runtime threads – they would stall the runtime
+/// (and `anchor_client` blocking calls panic there), so this offloads them to the tokio
+/// blocking thread pool. Use it to migrate gradually until everything moves to [`VrfClient`].
+///
+/// Note that the blocking program client owns a runtime of its own, so the last clone
+/// of the adapter should be dropped outside of async contexts as well.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use orao_solana_vrf::nonblocking::BlockingBridge;
+/// use std::sync::Arc;
+///
+/// # let payer: Arc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let bridge = BlockingBridge::new(program);
+/// let randomness = bridge
+///     .run(move |program| orao_solana_vrf::get_randomness(program, &seed))
+///     .await?;
+/// # Ok(()) }
+/// ```
+pub struct BlockingBridge<C> {
+    program: Arc<anchor_client::Program<C>>,
+}
+
+impl<C> Clone for BlockingBridge<C> {
+    fn clone(&self) -> Self {
+        Self {
+            program: self.program.clone(),
+        }
+    }
+}
+
+impl<C, S> BlockingBridge<C>
+where
+    C: std::ops::Deref<Target = S> + Clone + Send + Sync + 'static,
+    S: Signer,
+{
+    /// Creates an adapter of the given program client.
+    pub fn new(program: anchor_client::Program<C>) -> Self {
+        Self {
+            program: Arc::new(program),
+        }
+    }
+
+    /// Returns the program client (use it outside of async contexts only).
+    pub fn program(&self) -> &anchor_client::Program<C> {
+        &self.program
+    }
+
+    /// Runs the blocking function on the tokio blocking thread pool.
+    ///
+    /// Fails with a custom client error if the function panics.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, anchor_client::ClientError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&anchor_client::Program<C>) -> Result<T, anchor_client::ClientError>
+            + Send
+            + 'static,
+    {
+        let program = self.program.clone();
+        tokio::task::spawn_blocking(move || f(&program))
+            .await
+            .map_err(|err| {
+                anchor_client::solana_client::client_error::ClientError::from(
+                    ClientErrorKind::Custom(format!("Blocking task failed: {}", err)),
+                )
+            })?
+    }
+
+    /// Fetches VRF on-chain state (see [`crate::get_network_state`]).
+    pub async fn get_network_state(&self) -> Result<NetworkState, anchor_client::ClientError> {
+        self.run(|program| super::get_network_state(program)).await
+    }
+
+    /// Fetches randomness request state for the given seed (see [`crate::get_randomness`]).
+    pub async fn get_randomness(
+        &self,
+        seed: [u8; 32],
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        self.run(move |program| super::get_randomness(program, &seed))
+            .await
+    }
+}
+
+/// Decodes the randomness account and returns it if fulfilled.
+fn fulfilled_randomness(env: &Env, account: &Account) -> Option<Randomness> {
+    env.decode_randomness(account)
+        .ok()
+        .filter(|randomness| randomness.fulfilled().is_some())
+}
+
+/// Outcome of a successful request simulation (see [`VrfClient::simulate_request_randomness`]).
+#[derive(Debug, Clone)]
+pub struct RequestSimulation {
+    /// Compute units consumed by the transaction, if reported by the RPC.
+    pub units_consumed: Option<u64>,
+    /// Program logs of the transaction.
+    pub logs: Vec<String>,
+}
+
+/// Returns the SDK error with the transaction error and the program logs
+/// appended to its message.
+fn failure(
+    err: crate::SdkError,
+    transaction_error: impl std::fmt::Display,
+    logs: &[String],
+) -> anchor_client::ClientError {
+    let mut failure = anchor_lang::error::Error::from(err);
+    if let anchor_lang::error::Error::AnchorError(failure) = &mut failure {
+        failure.error_msg = format!(
+            "{}: {}\n{}",
+            failure.error_msg,
+            transaction_error,
+            logs.join("\n")
+        );
+    }
+    failure.into()
+}
+
+/// Counter of the RPC calls made by a [`VrfClient`].
+#[derive(Debug, Default)]
+struct RpcCallCounter {
+    calls: AtomicUsize,
+    warning_threshold: Option<usize>,
+}
+
+impl RpcCallCounter {
+    fn record(&self) {
+        let calls = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        if Some(calls) == self.warning_threshold {
+            log::warn!(
+                "VRF client has made {} RPC calls, the RPC might start throttling",
+                calls
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::{
+        solana_client::rpc_client::RpcClientConfig, solana_sdk::signer::keypair::Keypair,
+    };
+    use anchor_lang::AccountSerialize;
+    use serde_json::json;
+
+    use crate::{
+        replay::{RecordedCall, ReplaySender},
+        state::RandomnessResponse,
+        RANDOMNESS_ACCOUNT_SIZE,
+    };
+
+    use super::*;
+
+    /// The RPC client queries the node version before the first call with a commitment.
+    fn version() -> RecordedCall {
+        RecordedCall {
+            method: "getVersion".to_string(),
+            params: serde_json::Value::Null,
+            response: Ok(json!({ "solana-core": "1.16.15", "feature-set": 0 })),
+        }
+    }
+
+    /// `getAccountInfo` call of a [`VrfClient`] at the default commitment.
+    fn get_account_info(address: &Pubkey, account: Option<&Account>) -> RecordedCall {
+        let value = account.map(|account| {
+            UiAccount::encode(address, account, UiAccountEncoding::Base64, None, None)
+        });
+        RecordedCall {
+            method: "getAccountInfo".to_string(),
+            params: json!([
+                address.to_string(),
+                {
+                    "encoding": "base64+zstd",
+                    "dataSlice": null,
+                    "commitment": "finalized",
+                    "minContextSlot": null,
+                }
+            ]),
+            response: Ok(json!({ "context": { "slot": 1 }, "value": value })),
+        }
+    }
+
+    fn client(calls: Vec<RecordedCall>) -> VrfClient {
+        VrfClient::new(RpcClient::new_sender(
+            ReplaySender::new(calls),
+            RpcClientConfig::default(),
+        ))
+    }
+
+    fn randomness() -> Randomness {
+        Randomness {
+            seed: [1; 32],
+            randomness: [2; 64],
+            responses: vec![RandomnessResponse {
+                pubkey: Pubkey::new_from_array([4; 32]),
+                randomness: [3; 64],
+            }],
+        }
+    }
+
+    fn randomness_account() -> Account {
+        let mut data = Vec::new();
+        randomness().try_serialize(&mut data).unwrap();
+        data.resize(RANDOMNESS_ACCOUNT_SIZE, 0);
+        Account {
+            lamports: 1,
+            data,
+            owner: crate::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn get_randomness_decodes_replayed_account() {
+        let address = Env::default().randomness_account_address(&[1; 32]);
+        let client = client(vec![
+            version(),
+            get_account_info(&address, Some(&randomness_account())),
+            get_account_info(&address, None),
+        ]);
+
+        assert_eq!(client.get_randomness(&[1; 32]).await.unwrap(), randomness());
+        assert!(matches!(
+            client.get_randomness(&[1; 32]).await,
+            Err(anchor_client::ClientError::AccountNotFound)
+        ));
+        assert_eq!(client.rpc_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn request_randomness_rejects_seed_in_use() {
+        let address = Env::default().randomness_account_address(&[1; 32]);
+        let client = client(vec![
+            version(),
+            get_account_info(&address, Some(&randomness_account())),
+        ]);
+
+        let err = client
+            .request_randomness(&Keypair::new(), &RequestBuilder::new([1; 32]))
+            .await
+            .unwrap_err();
+        assert!(crate::Error::SeedAlreadyInUse.matches(&err));
+    }
+}
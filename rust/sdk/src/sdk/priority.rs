@@ -0,0 +1,112 @@
+use anchor_client::solana_sdk::{signature::Signature, signer::Signer};
+use anchor_lang::prelude::Pubkey;
+
+use std::ops::Deref;
+
+use super::RequestBuilder;
+
+/// Request priority level (see [`crate::RequestBuilder::with_fee_level`]).
+///
+/// Every level maps to a fixed compute unit price – use it as a simple knob for
+/// congested periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeLevel {
+    /// No priority fee.
+    #[default]
+    None,
+    /// 1 000 micro-lamports per compute unit.
+    Low,
+    /// 10 000 micro-lamports per compute unit.
+    Medium,
+    /// 100 000 micro-lamports per compute unit.
+    High,
+}
+
+impl FeeLevel {
+    /// Returns the compute unit price (in micro-lamports) of this level.
+    ///
+    /// Returns `None` for [`FeeLevel::None`].
+    pub fn compute_unit_price(&self) -> Option<u64> {
+        match self {
+            FeeLevel::None => None,
+            FeeLevel::Low => Some(1_000),
+            FeeLevel::Medium => Some(10_000),
+            FeeLevel::High => Some(100_000),
+        }
+    }
+}
+
+/// Returns the given percentile of the compute unit prices (in micro-lamports)
+/// paid by recent transactions that locked any of the `accounts`.
+///
+/// Uses the nearest-rank percentile of the `getRecentPrioritizationFees` results;
+/// `percentile` is capped at 100. Returns zero if there is no data.
+pub fn recent_fee_percentile<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    accounts: &[Pubkey],
+    percentile: u8,
+) -> Result<u64, anchor_client::ClientError> {
+    let fees = orao_vrf
+        .rpc()
+        .get_recent_prioritization_fees(accounts)?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    Ok(fee_percentile(fees, percentile))
+}
+
+/// Requests randomness paying priority fee at the given percentile of the recent fees
+/// (see [`RequestBuilder::with_recent_fee_percentile`]).
+///
+/// Returns the signature of the request transaction.
+pub fn request_randomness_auto_fee<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    request: RequestBuilder,
+    percentile: u8,
+) -> Result<Signature, anchor_client::ClientError> {
+    request
+        .with_recent_fee_percentile(orao_vrf, percentile)?
+        .build(orao_vrf)?
+        .send()
+}
+
+/// Returns the nearest-rank `percentile` (capped at 100) of the `fees`,
+/// or zero if there are none.
+pub(super) fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() * usize::from(percentile.min(100))).div_ceil(100);
+    fees[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_percentile_of_no_fees() {
+        assert_eq!(fee_percentile(vec![], 0), 0);
+        assert_eq!(fee_percentile(vec![], 50), 0);
+        assert_eq!(fee_percentile(vec![], 100), 0);
+    }
+
+    #[test]
+    fn fee_percentile_nearest_rank() {
+        let fees = vec![50, 10, 40, 20, 30];
+        assert_eq!(fee_percentile(fees.clone(), 0), 10);
+        assert_eq!(fee_percentile(fees.clone(), 50), 30);
+        assert_eq!(fee_percentile(fees.clone(), 100), 50);
+        // capped at 100
+        assert_eq!(fee_percentile(fees.clone(), 101), 50);
+        assert_eq!(fee_percentile(fees, u8::MAX), 50);
+    }
+
+    #[test]
+    fn fee_percentile_sorts_fees() {
+        assert_eq!(fee_percentile(vec![7, 3, 9, 1], 50), 3);
+        assert_eq!(fee_percentile(vec![7, 3, 9, 1], 75), 7);
+        assert_eq!(fee_percentile(vec![5], 1), 5);
+    }
+}
@@ -0,0 +1,286 @@
+//! Recording and replaying of RPC interactions for deterministic tests.
+//!
+//! [`RecordingSender`] captures every RPC request/response pair of a real session,
+//! and [`ReplaySender`] serves the captured responses without a network. Both plug
+//! into the nonblocking RPC client, so a [`super::nonblocking::VrfClient`] could be
+//! built on top of either:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use anchor_client::solana_client::{
+//!     nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig,
+//! };
+//! use orao_solana_vrf::{
+//!     nonblocking::VrfClient,
+//!     replay::{RecordingSender, ReplaySender},
+//! };
+//!
+//! # let seed: [u8; 32] = panic!();
+//! // capture a devnet session once
+//! let (sender, recording) = RecordingSender::http("https://api.devnet.solana.com");
+//! let client = VrfClient::new(RpcClient::new_sender(sender, RpcClientConfig::default()));
+//! client.get_randomness(&seed).await?;
+//! recording.save("session.json")?;
+//!
+//! // and replay it offline
+//! let sender = ReplaySender::load("session.json")?;
+//! let client = VrfClient::new(RpcClient::new_sender(sender, RpcClientConfig::default()));
+//! client.get_randomness(&seed).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! Replay only works for sessions that make the same calls with the same parameters,
+//! e.g. transactions have to be built from the recorded blockhash and signed with
+//! the same keys.
+//!
+//! **Note:** requires `async` feature to be enabled.
+
+use anchor_client::solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use async_trait::async_trait;
+use solana_rpc_client::http_sender::HttpSender;
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// RPC request along with its response (see [`RecordingSender`]).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedCall {
+    /// RPC method name, e.g. `getAccountInfo`.
+    pub method: String,
+    /// Request parameters.
+    pub params: serde_json::Value,
+    /// Response, or the error message if the request failed.
+    pub response: Result<serde_json::Value, String>,
+}
+
+/// RPC sender that records every call made through the wrapped sender.
+///
+/// The recorded calls are available through the [`Recording`] handle
+/// (the sender itself is owned by the RPC client).
+pub struct RecordingSender<S> {
+    inner: S,
+    calls: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl<S: RpcSender> RecordingSender<S> {
+    /// Wraps the given sender. Returns the sender along with the handle to its recording.
+    pub fn new(inner: S) -> (Self, Recording) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recording = Recording {
+            calls: calls.clone(),
+        };
+        (Self { inner, calls }, recording)
+    }
+}
+
+impl RecordingSender<HttpSender> {
+    /// Records calls to the RPC at the given URL.
+    pub fn http(url: impl ToString) -> (Self, Recording) {
+        Self::new(HttpSender::new(url))
+    }
+}
+
+#[async_trait]
+impl<S: RpcSender + Send + Sync> RpcSender for RecordingSender<S> {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let response = self.inner.send(request, params.clone()).await;
+        self.calls
+            .lock()
+            .expect("poisoned recording")
+            .push(RecordedCall {
+                method: request.to_string(),
+                params,
+                response: response
+                    .as_ref()
+                    .map(Clone::clone)
+                    .map_err(ToString::to_string),
+            });
+        response
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+/// Handle to the calls recorded by a [`RecordingSender`].
+#[derive(Debug, Clone)]
+pub struct Recording {
+    calls: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl Recording {
+    /// Returns the calls recorded so far, in the order of their completion.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().expect("poisoned recording").clone()
+    }
+
+    /// Saves the calls recorded so far to the given file as JSON
+    /// (see [`ReplaySender::load`]).
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(file, &self.calls())?;
+        Ok(())
+    }
+}
+
+/// RPC sender that serves recorded responses (see [`RecordingSender`]).
+///
+/// Every recorded call is served once: a request gets the response of the earliest
+/// unserved call with the same method and parameters, so repeated calls (e.g. polling)
+/// get their responses in the recorded order. Requests that were not recorded fail
+/// with a custom client error. Recorded errors are replayed as custom client errors
+/// with the recorded message.
+#[derive(Debug)]
+pub struct ReplaySender {
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+impl ReplaySender {
+    /// Creates a sender that serves the given calls.
+    pub fn new(calls: Vec<RecordedCall>) -> Self {
+        Self {
+            calls: Mutex::new(calls),
+        }
+    }
+
+    /// Loads calls saved by [`Recording::save`].
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        Ok(Self::new(serde_json::from_reader(file)?))
+    }
+
+    /// Returns the number of recorded calls not yet served.
+    pub fn remaining(&self) -> usize {
+        self.calls.lock().expect("poisoned replay").len()
+    }
+}
+
+#[async_trait]
+impl RpcSender for ReplaySender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let method = request.to_string();
+        let mut calls = self.calls.lock().expect("poisoned replay");
+        let position = calls
+            .iter()
+            .position(|call| call.method == method && call.params == params)
+            .ok_or_else(|| {
+                ClientError::from(ClientErrorKind::Custom(format!(
+                    "No recorded response for {} {}",
+                    method, params
+                )))
+            })?;
+        calls
+            .remove(position)
+            .response
+            .map_err(|err| ClientErrorKind::Custom(err).into())
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "replay".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::{
+        solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig},
+        solana_sdk::commitment_config::CommitmentConfig,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn call(
+        params: serde_json::Value,
+        response: Result<serde_json::Value, String>,
+    ) -> RecordedCall {
+        RecordedCall {
+            method: "getSlot".to_string(),
+            params,
+            response,
+        }
+    }
+
+    /// The RPC client queries the node version before the first call with a commitment.
+    fn version() -> RecordedCall {
+        RecordedCall {
+            method: "getVersion".to_string(),
+            params: serde_json::Value::Null,
+            response: Ok(json!({ "solana-core": "1.16.15", "feature-set": 0 })),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_responses_in_order() {
+        let sender = ReplaySender::new(vec![
+            version(),
+            call(json!([{ "commitment": "finalized" }]), Ok(json!(1))),
+            call(json!([{ "commitment": "finalized" }]), Ok(json!(2))),
+        ]);
+        let rpc = RpcClient::new_sender(sender, RpcClientConfig::default());
+
+        assert_eq!(rpc.get_slot().await.unwrap(), 1);
+        assert_eq!(rpc.get_slot().await.unwrap(), 2);
+        let err = rpc.get_slot().await.unwrap_err();
+        assert!(matches!(err.kind(), ClientErrorKind::Custom(_)));
+    }
+
+    #[tokio::test]
+    async fn records_calls_of_the_wrapped_sender() {
+        let (sender, recording) = RecordingSender::new(ReplaySender::new(vec![
+            version(),
+            call(json!([{ "commitment": "finalized" }]), Ok(json!(7))),
+            call(
+                json!([{ "commitment": "processed" }]),
+                Err("boom".to_string()),
+            ),
+        ]));
+        let rpc = RpcClient::new_sender(sender, RpcClientConfig::default());
+
+        assert_eq!(rpc.get_slot().await.unwrap(), 7);
+        assert!(rpc
+            .get_slot_with_commitment(CommitmentConfig::processed())
+            .await
+            .is_err());
+        let calls = recording.calls();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], version());
+        assert_eq!(calls[1].method, "getSlot");
+        assert_eq!(calls[1].response, Ok(json!(7)));
+        assert!(calls[2].response.as_ref().unwrap_err().contains("boom"));
+    }
+
+    #[test]
+    fn recording_round_trips_through_json() {
+        let calls = vec![version(), call(json!([]), Err("boom".to_string()))];
+        let json = serde_json::to_string(&calls).unwrap();
+        let sender = ReplaySender::new(serde_json::from_str(&json).unwrap());
+        assert_eq!(sender.remaining(), 2);
+        assert_eq!(*sender.calls.lock().unwrap(), calls);
+    }
+}
@@ -0,0 +1,177 @@
+use anchor_client::{
+    solana_client::{client_error::ClientError, rpc_client::RpcClient},
+    solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, signer::Signer},
+};
+use anchor_lang::prelude::Pubkey;
+
+use crate::{randomness_account_address_for, state::Randomness, SdkError};
+
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+use super::{
+    get_randomness,
+    history::{successful_signatures, ConfirmedSignature},
+    FulfillmentWaiter, RequestBuilder,
+};
+
+/// How long to wait for an in-flight request transaction to get confirmed.
+const IN_FLIGHT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval between lookups of an in-flight request transaction.
+const IN_FLIGHT_REQUEST_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sends the request unless a request for the same seed already exists.
+///
+/// Returns the signature of the request transaction – either the new one or the one
+/// that created the existing request. If the existing request transaction is not yet
+/// confirmed (i.e. another client just sent it), then this waits for its confirmation
+/// instead of sending a duplicate, and fails with [`SdkError::Timeout`] if it is not
+/// confirmed in time.
+///
+/// The seed uniquely identifies the request on-chain, so this could be used to
+/// deduplicate requests across many clients sharing a seed.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let request = orao_solana_vrf::RequestBuilder::new(seed);
+/// let tx = orao_solana_vrf::request_idempotent(&program, request)?;
+///
+/// println!("Your transaction is {}", tx);
+/// # Ok(()) }
+/// ```
+pub fn request_idempotent<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    request: RequestBuilder,
+) -> Result<Signature, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let address = randomness_account_address_for(&orao_vrf.id(), request.seed());
+
+    let exists = |rpc: &RpcClient| -> Result<bool, ClientError> {
+        Ok(rpc
+            .get_account_with_commitment(&address, CommitmentConfig::processed())?
+            .value
+            .is_some())
+    };
+
+    if !exists(&rpc)? {
+        match request.build(orao_vrf)?.send() {
+            Ok(signature) => return Ok(signature),
+            // someone else might have created the request in the meantime
+            Err(err) if !exists(&rpc)? => return Err(err),
+            Err(_) => (),
+        }
+    }
+
+    let deadline = Instant::now() + IN_FLIGHT_REQUEST_TIMEOUT;
+    loop {
+        if let Some(request) = request_transaction(&rpc, &address)? {
+            return Ok(request.signature);
+        }
+        if Instant::now() >= deadline {
+            return Err(SdkError::Timeout.into());
+        }
+        std::thread::sleep(IN_FLIGHT_REQUEST_POLL_INTERVAL);
+    }
+}
+
+/// Returns fulfilled randomness for the seed of the request, requesting it if necessary.
+///
+/// * if the randomness is already fulfilled, then it is returned as-is,
+/// * if the request is pending, then this waits for the fulfillment
+///   (see [`FulfillmentWaiter`]),
+/// * if there is no request yet, then this sends it (see [`request_idempotent`])
+///   and waits for the fulfillment.
+///
+/// Errors other than the missing randomness account are never taken for "not requested".
+/// Fails with [`SdkError::Timeout`] if the randomness is not fulfilled within the `timeout`
+/// (counted once the request exists).
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use std::time::Duration;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let request = orao_solana_vrf::RequestBuilder::new(seed);
+/// let randomness =
+///     orao_solana_vrf::get_or_request(&program, request, Duration::from_secs(60))?;
+///
+/// println!("Randomness fulfilled: {:?}", randomness.fulfilled());
+/// # Ok(()) }
+/// ```
+pub fn get_or_request<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    request: RequestBuilder,
+    timeout: Duration,
+) -> Result<Randomness, anchor_client::ClientError> {
+    let seed = *request.seed();
+
+    match get_randomness(orao_vrf, &seed) {
+        Ok(randomness) if randomness.fulfilled().is_some() => return Ok(randomness),
+        Ok(_) => (),
+        Err(anchor_client::ClientError::AccountNotFound) => {
+            request_idempotent(orao_vrf, request)?;
+        }
+        Err(err) => return Err(err),
+    }
+
+    FulfillmentWaiter::new(seed)
+        .with_timeout(timeout)
+        .wait(orao_vrf)
+}
+
+/// Sends the same request to many VRF deployments (e.g. a primary and a backup one).
+///
+/// Returns the outcome for every deployment in the given order. Use
+/// [`crate::FulfillmentWaiter::wait_any`] to wait for the first fulfillment.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let backup_id: solana_sdk::pubkey::Pubkey = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let primary = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+/// let backup = client.program(backup_id).expect("unable to get a program");
+///
+/// let seed = orao_solana_vrf::random_seed();
+/// let request = orao_solana_vrf::RequestBuilder::new(seed);
+/// orao_solana_vrf::request_randomness_multi(&[&primary, &backup], request);
+///
+/// let (index, randomness) = orao_solana_vrf::FulfillmentWaiter::new(seed)
+///     .wait_any(&[&primary, &backup])?;
+/// # Ok(()) }
+/// ```
+pub fn request_randomness_multi<C: Deref<Target = impl Signer> + Clone>(
+    deployments: &[&anchor_client::Program<C>],
+    request: RequestBuilder,
+) -> Vec<Result<Signature, anchor_client::ClientError>> {
+    deployments
+        .iter()
+        .map(|orao_vrf| request.clone().build(orao_vrf)?.send())
+        .collect()
+}
+
+/// Returns the earliest successful confirmed transaction of the randomness account,
+/// i.e. the request transaction.
+pub(super) fn request_transaction(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<ConfirmedSignature>, ClientError> {
+    Ok(successful_signatures(rpc, address, None)?.pop())
+}
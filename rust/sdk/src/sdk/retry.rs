@@ -0,0 +1,179 @@
+use anchor_client::solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_custom_error::{
+        JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+        JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED, JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    },
+    rpc_request::RpcError,
+};
+
+use std::time::Duration;
+
+/// Retry policy for transient RPC failures.
+///
+/// Only transient failures are retried (see [`RetryPolicy::is_retryable`]) – deterministic
+/// ones (e.g. a missing account or a failed transaction) are returned immediately.
+/// The delay doubles after every retry starting at `base_delay`.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use orao_solana_vrf::RetryPolicy;
+/// use std::time::Duration;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let policy = RetryPolicy {
+///     max_retries: 5,
+///     base_delay: Duration::from_millis(200),
+/// };
+/// let randomness = policy.run(|| orao_solana_vrf::get_randomness(&program, &seed))?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries (zero disables retries).
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// No retries.
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Calls `f` until it succeeds, fails with a non-retryable error,
+    /// or the retries are exhausted (blocking).
+    pub fn run<T>(
+        &self,
+        mut f: impl FnMut() -> Result<T, anchor_client::ClientError>,
+    ) -> Result<T, anchor_client::ClientError> {
+        let mut attempt = 0;
+        loop {
+            match f() {
+                Err(err) if attempt < self.max_retries && Self::is_retryable(&err) => {
+                    std::thread::sleep(self.delay(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the delay before the retry number `attempt` (starting at zero).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay.saturating_mul(1 << attempt.min(16))
+    }
+
+    /// Checks whether the error is a transient RPC failure worth retrying:
+    ///
+    /// * I/O errors, connection failures and timeouts,
+    /// * HTTP `429 Too Many Requests` and `5xx` responses,
+    /// * RPC node being unhealthy or behind (e.g. the `minContextSlot` is not yet reached,
+    ///   or the block is not yet available).
+    ///
+    /// Everything else (e.g. [`anchor_client::ClientError::AccountNotFound`], signing errors,
+    /// transaction errors and preflight failures) is deterministic.
+    pub fn is_retryable(err: &anchor_client::ClientError) -> bool {
+        match err {
+            anchor_client::ClientError::SolanaClientError(err) => is_transient(err),
+            _ => false,
+        }
+    }
+}
+
+/// Checks whether the RPC client error is transient (see [`RetryPolicy::is_retryable`]).
+pub(super) fn is_transient(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(err) => {
+            err.is_timeout()
+                || err.is_connect()
+                || err
+                    .status()
+                    .is_some_and(|status| status.as_u16() == 429 || status.is_server_error())
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => matches!(
+            *code,
+            JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+                | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_client::{
+        rpc_custom_error::JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+        rpc_request::RpcResponseErrorData,
+    };
+
+    use super::*;
+
+    fn rpc_error(code: i64) -> anchor_client::ClientError {
+        ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code,
+            message: "error".to_string(),
+            data: RpcResponseErrorData::Empty,
+        }))
+        .into()
+    }
+
+    #[test]
+    fn io_errors_are_retryable() {
+        let err = ClientError::from(ClientErrorKind::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset,
+        )));
+        assert!(RetryPolicy::is_retryable(&err.into()));
+    }
+
+    #[test]
+    fn lagging_node_errors_are_retryable() {
+        for code in [
+            JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+            JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+            JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+        ] {
+            assert!(RetryPolicy::is_retryable(&rpc_error(code)), "{}", code);
+        }
+    }
+
+    #[test]
+    fn deterministic_errors_are_not_retryable() {
+        assert!(!RetryPolicy::is_retryable(&rpc_error(
+            JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE
+        )));
+        assert!(!RetryPolicy::is_retryable(
+            &anchor_client::ClientError::AccountNotFound
+        ));
+    }
+
+    #[test]
+    fn delay_doubles_and_saturates() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(u32::MAX), policy.delay(16));
+
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::MAX,
+        };
+        assert_eq!(policy.delay(u32::MAX), Duration::MAX);
+    }
+}
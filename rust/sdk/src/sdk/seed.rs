@@ -0,0 +1,132 @@
+use anchor_client::{
+    solana_client::client_error::{ClientError, ClientErrorKind},
+    solana_sdk::{account::from_account, signer::Signer, slot_hashes::SlotHashes, sysvar},
+};
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::hash::{hashv, Hash},
+};
+use rand::{rngs::OsRng, RngCore};
+
+use std::ops::Deref;
+
+/// Returns a random seed drawn from the operating system CSPRNG.
+///
+/// A seed is just 32 bytes – it need not correspond to any account (so there is
+/// no need to generate a keypair for it), but it must be unique per request
+/// because it identifies the randomness account. Application-defined 32 bytes
+/// (e.g. a game round id) could be used as a seed as-is, see [`seed_from_data`]
+/// to derive a seed from data of arbitrary length.
+pub fn random_seed() -> [u8; 32] {
+    let mut seed = [0; 32];
+    OsRng.fill_bytes(&mut seed);
+    seed
+}
+
+/// Derives a deterministic seed from application data, e.g. a game name and round id.
+///
+/// The seed is `sha256` of the `parts`, each prefixed with its length as 8 little-endian
+/// bytes, so that splitting the same bytes differently (e.g. `["ab", "c"]` and
+/// `["a", "bc"]`) gives different seeds. The data must be unique per request
+/// (see [`random_seed`]), and note that anyone knowing the data could derive the seed
+/// (and so look up the randomness) in advance.
+pub fn seed_from_data(parts: &[&[u8]]) -> [u8; 32] {
+    let lengths = parts
+        .iter()
+        .map(|part| (part.len() as u64).to_le_bytes())
+        .collect::<Vec<_>>();
+    let prefixed = lengths
+        .iter()
+        .zip(parts)
+        .flat_map(|(length, part)| [length.as_slice(), part])
+        .collect::<Vec<_>>();
+    hashv(&prefixed).to_bytes()
+}
+
+/// Seed derived from a slot hash (see [`seed_from_slot_hash`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotHashSeed {
+    /// Slot number.
+    pub slot: u64,
+    /// Hash of the slot (as recorded in the `SlotHashes` sysvar).
+    pub hash: Hash,
+    /// Derived seed.
+    pub seed: [u8; 32],
+}
+
+/// Derives a seed from the slot hash.
+///
+/// The seed is `sha256(slot || hash)` where `slot` is the slot number
+/// as 8 little-endian bytes and `hash` is the 32-byte slot hash,
+/// so anyone could re-derive it from the chain history.
+pub fn seed_from_slot_hash(slot: u64, hash: &Hash) -> [u8; 32] {
+    hashv(&[&slot.to_le_bytes(), hash.as_ref()]).to_bytes()
+}
+
+/// Derives a seed from the most recent slot hash of the `SlotHashes` sysvar
+/// (see [`seed_from_slot_hash`]).
+///
+/// The slot hash is not chosen by the requester, so such a seed demonstrably
+/// comes from the chain history. Note that seeds are unique, so only one request
+/// is possible per slot hash.
+pub fn recent_slot_hash_seed<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+) -> Result<SlotHashSeed, anchor_client::ClientError> {
+    let account = orao_vrf.rpc().get_account(&sysvar::slot_hashes::ID)?;
+    let slot_hashes = from_account::<SlotHashes, _>(&account).ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(
+            "Unable to decode the SlotHashes sysvar".into(),
+        ))
+    })?;
+    let (slot, hash) = slot_hashes.first().copied().ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(
+            "The SlotHashes sysvar is empty".into(),
+        ))
+    })?;
+
+    Ok(SlotHashSeed {
+        slot,
+        hash,
+        seed: seed_from_slot_hash(slot, &hash),
+    })
+}
+
+/// Seed representations for audit reports and other artifacts, so that
+/// the seed is consumable by any downstream verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeedRepr {
+    /// Base58 encoding (same as the one of a public key).
+    pub base58: String,
+    /// Lowercase hex encoding.
+    pub hex: String,
+    /// Raw seed bytes.
+    pub bytes: [u8; 32],
+}
+
+impl From<[u8; 32]> for SeedRepr {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self {
+            base58: Pubkey::new_from_array(bytes).to_string(),
+            hex: bytes.iter().map(|x| format!("{:02x}", x)).collect(),
+            bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_from_data_separates_parts() {
+        assert_ne!(
+            seed_from_data(&[b"ab", b"c"]),
+            seed_from_data(&[b"a", b"bc"])
+        );
+        assert_ne!(seed_from_data(&[b"abc"]), seed_from_data(&[b"abc", b""]));
+        assert_eq!(
+            seed_from_data(&[b"ab", b"c"]),
+            seed_from_data(&[b"ab", b"c"])
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use anchor_client::solana_sdk::signer::Signer;
+use anchor_lang::{
+    error::{Error, ErrorCode},
+    prelude::borsh,
+    AnchorDeserialize, AnchorSerialize,
+};
+
+use crate::{randomness_account_address_for, state::Randomness};
+
+use std::ops::Deref;
+
+use super::MAX_MULTIPLE_ACCOUNTS;
+
+/// Randomness state of many seeds at some point in time (see [`export_state`]).
+///
+/// Serializable with Borsh (see [`RandomnessSnapshot::to_bytes`]), so that a service
+/// could back up its view of outstanding and completed requests and restore it later
+/// (see [`import_state`]).
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RandomnessSnapshot {
+    /// Every seed along with its randomness state, `None` if not requested.
+    pub entries: Vec<([u8; 32], Option<Randomness>)>,
+}
+
+impl RandomnessSnapshot {
+    /// Serializes the snapshot.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.try_to_vec()
+            .expect("serialization into a vector never fails")
+    }
+
+    /// Deserializes a snapshot serialized with [`RandomnessSnapshot::to_bytes`].
+    ///
+    /// Fails if the data is truncated or malformed, or has trailing bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, anchor_client::ClientError> {
+        Self::try_from_slice(data)
+            .map_err(|_| Error::from(ErrorCode::AccountDidNotDeserialize).into())
+    }
+}
+
+/// Outcome of a snapshot import (see [`import_state`]).
+#[derive(Debug, Clone)]
+pub struct ImportedSnapshot {
+    /// Current randomness state of the snapshot seeds (in the snapshot order).
+    pub current: RandomnessSnapshot,
+    /// Seeds whose current state contradicts the snapshot, e.g. the recorded
+    /// request is gone or the fulfilled randomness differs.
+    pub inconsistent: Vec<[u8; 32]>,
+}
+
+/// Fetches randomness state of the given seeds into a snapshot.
+///
+/// Accounts are fetched in batches of [`MAX_MULTIPLE_ACCOUNTS`].
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seeds: Vec<[u8; 32]> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let snapshot = orao_solana_vrf::export_state(&program, &seeds)?;
+/// std::fs::write("vrf-snapshot.bin", snapshot.to_bytes())?;
+///
+/// // later on
+/// let snapshot =
+///     orao_solana_vrf::RandomnessSnapshot::from_bytes(&std::fs::read("vrf-snapshot.bin")?)?;
+/// let imported = orao_solana_vrf::import_state(&program, snapshot)?;
+/// assert!(imported.inconsistent.is_empty());
+/// # Ok(()) }
+/// ```
+pub fn export_state<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seeds: &[[u8; 32]],
+) -> Result<RandomnessSnapshot, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let mut entries = Vec::with_capacity(seeds.len());
+    for chunk in seeds.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses = chunk
+            .iter()
+            .map(|seed| randomness_account_address_for(&orao_vrf.id(), seed))
+            .collect::<Vec<_>>();
+        let accounts = rpc.get_multiple_accounts(&addresses)?;
+        for (seed, account) in chunk.iter().zip(accounts) {
+            let randomness = account
+                .map(|account| Randomness::decode_from_account(&orao_vrf.id(), &account))
+                .transpose()?;
+            entries.push((*seed, randomness));
+        }
+    }
+    Ok(RandomnessSnapshot { entries })
+}
+
+/// Validates the snapshot against the chain and returns the current state.
+///
+/// The state is expected to only move forward since the snapshot was taken:
+/// a missing request might have been made, a pending one might have received more
+/// responses or got fulfilled, but a recorded request never disappears and recorded
+/// responses never change.
+pub fn import_state<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    snapshot: RandomnessSnapshot,
+) -> Result<ImportedSnapshot, anchor_client::ClientError> {
+    let seeds = snapshot
+        .entries
+        .iter()
+        .map(|(seed, _)| *seed)
+        .collect::<Vec<_>>();
+    let current = export_state(orao_vrf, &seeds)?;
+
+    let inconsistent = snapshot
+        .entries
+        .iter()
+        .zip(current.entries.iter())
+        .filter(|((_, recorded), (_, current))| !is_consistent(recorded, current))
+        .map(|((seed, _), _)| *seed)
+        .collect();
+
+    Ok(ImportedSnapshot {
+        current,
+        inconsistent,
+    })
+}
+
+/// Returns `true` if the `current` state could follow the `recorded` one.
+fn is_consistent(recorded: &Option<Randomness>, current: &Option<Randomness>) -> bool {
+    match (recorded, current) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(recorded), Some(current)) => {
+            recorded.seed == current.seed
+                && recorded.responses.len() <= current.responses.len()
+                && recorded
+                    .responses
+                    .iter()
+                    .zip(current.responses.iter())
+                    .all(|(a, b)| a.pubkey == b.pubkey && a.randomness == b.randomness)
+                && (recorded.fulfilled().is_none() || recorded.randomness == current.randomness)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::state::RandomnessResponse;
+
+    use super::*;
+
+    fn response(byte: u8) -> RandomnessResponse {
+        RandomnessResponse {
+            pubkey: Pubkey::new_from_array([byte; 32]),
+            randomness: [byte; 64],
+        }
+    }
+
+    fn pending(responses: Vec<RandomnessResponse>) -> Randomness {
+        Randomness {
+            seed: [1; 32],
+            randomness: [0; 64],
+            responses,
+        }
+    }
+
+    fn fulfilled(randomness: [u8; 64]) -> Randomness {
+        Randomness {
+            randomness,
+            ..pending(vec![response(4), response(5)])
+        }
+    }
+
+    fn snapshot() -> RandomnessSnapshot {
+        RandomnessSnapshot {
+            entries: vec![
+                ([1; 32], Some(fulfilled([2; 64]))),
+                ([2; 32], Some(pending(vec![response(4)]))),
+                ([3; 32], None),
+            ],
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_bytes() {
+        let bytes = snapshot().to_bytes();
+        let restored = RandomnessSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.entries, snapshot().entries);
+    }
+
+    #[test]
+    fn tampered_snapshot_is_rejected() {
+        let bytes = snapshot().to_bytes();
+
+        let mut tampered = bytes.clone();
+        // the `Option` tag of the first entry, right after the length and the seed
+        tampered[4 + 32] = 2;
+        assert!(RandomnessSnapshot::from_bytes(&tampered).is_err());
+
+        assert!(RandomnessSnapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut extended = bytes;
+        extended.push(0);
+        assert!(RandomnessSnapshot::from_bytes(&extended).is_err());
+    }
+
+    #[test]
+    fn state_is_consistent_when_moving_forward() {
+        let requested = Some(pending(vec![]));
+        let responded = Some(pending(vec![response(4)]));
+        let done = Some(fulfilled([2; 64]));
+
+        assert!(is_consistent(&None, &None));
+        assert!(is_consistent(&None, &done));
+        assert!(is_consistent(&requested, &responded));
+        assert!(is_consistent(&responded, &done));
+        assert!(is_consistent(&done, &done));
+    }
+
+    #[test]
+    fn state_is_inconsistent_when_contradicting_the_snapshot() {
+        let responded = Some(pending(vec![response(4)]));
+        let done = Some(fulfilled([2; 64]));
+
+        // the request is gone
+        assert!(!is_consistent(&responded, &None));
+        // responses got lost or changed
+        assert!(!is_consistent(&done, &responded));
+        assert!(!is_consistent(
+            &responded,
+            &Some(pending(vec![response(5)]))
+        ));
+        // the fulfilled randomness differs
+        assert!(!is_consistent(&done, &Some(fulfilled([3; 64]))));
+        // the request is for another seed
+        let other = Randomness {
+            seed: [9; 32],
+            ..pending(vec![response(4)])
+        };
+        assert!(!is_consistent(&responded, &Some(other)));
+    }
+}
@@ -0,0 +1,218 @@
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        pubsub_client::{PubsubAccountClientSubscription, PubsubClient},
+        rpc_config::RpcAccountInfoConfig,
+        rpc_response::Response as RpcResponse,
+    },
+    solana_sdk::{account::Account, signer::Signer},
+};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+
+use crate::state::Randomness;
+
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+use super::{get_randomness_with_env, Env};
+
+/// Subscription to the fulfillment of a randomness request (see [`subscribe_fulfillment`]).
+///
+/// The websocket subscription is closed on drop.
+pub struct FulfillmentSubscription {
+    env: Env,
+    fulfilled: Option<Randomness>,
+    receiver: crossbeam_channel::Receiver<RpcResponse<UiAccount>>,
+    _subscription: PubsubAccountClientSubscription,
+}
+
+impl FulfillmentSubscription {
+    /// Blocks until the randomness is fulfilled.
+    ///
+    /// Fails with a custom client error if the subscription is closed by the RPC.
+    pub fn recv(&mut self) -> Result<Randomness, anchor_client::ClientError> {
+        if let Some(randomness) = self.fulfilled.take() {
+            return Ok(randomness);
+        }
+        while let Ok(update) = self.receiver.recv() {
+            if let Some(randomness) = self.decode(update) {
+                return Ok(randomness);
+            }
+        }
+        Err(subscription_closed())
+    }
+
+    /// Blocks until the randomness is fulfilled or the `timeout` elapses.
+    ///
+    /// Returns `None` on timeout.
+    pub fn recv_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<Randomness>, anchor_client::ClientError> {
+        if let Some(randomness) = self.fulfilled.take() {
+            return Ok(Some(randomness));
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.receiver.recv_timeout(remaining) {
+                Ok(update) => {
+                    if let Some(randomness) = self.decode(update) {
+                        return Ok(Some(randomness));
+                    }
+                }
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                    return Err(subscription_closed())
+                }
+            }
+        }
+    }
+
+    /// Decodes the account notification and returns the randomness if fulfilled.
+    fn decode(&self, update: RpcResponse<UiAccount>) -> Option<Randomness> {
+        let account = update.value.decode::<Account>()?;
+        self.env
+            .decode_randomness(&account)
+            .ok()
+            .filter(|randomness| randomness.fulfilled().is_some())
+    }
+}
+
+/// Subscribes to the randomness account of the given seed via websocket
+/// (`accountSubscribe`), so the fulfillment is observed as soon as it happens,
+/// without polling.
+///
+/// The websocket URL is derived from the RPC URL of the client (see [`websocket_url`]).
+/// Already fulfilled randomness is returned by the first [`FulfillmentSubscription::recv`].
+/// See `nonblocking::VrfClient::stream_fulfillments` for the async counterpart.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let mut subscription = orao_solana_vrf::subscribe_fulfillment(&program, &seed)?;
+/// let randomness = subscription.recv()?;
+/// println!("Randomness fulfilled: {:?}", randomness.fulfilled());
+/// # Ok(()) }
+/// ```
+pub fn subscribe_fulfillment<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+) -> Result<FulfillmentSubscription, anchor_client::ClientError> {
+    subscribe_fulfillment_with_env(orao_vrf, Env::new(orao_vrf.id()), seed)
+}
+
+/// Subscribes to the randomness account of the given seed of the deployment described
+/// by the `env` (see [`subscribe_fulfillment`] and [`Env`]).
+pub fn subscribe_fulfillment_with_env<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    env: Env,
+    seed: &[u8; 32],
+) -> Result<FulfillmentSubscription, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let address = env.randomness_account_address(seed);
+    let (subscription, receiver) = PubsubClient::account_subscribe(
+        &websocket_url(&rpc.url()),
+        &address,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..Default::default()
+        }),
+    )?;
+
+    // subscribe before looking at the current state so that no update is missed
+    let fulfilled = match get_randomness_with_env(orao_vrf, &env, seed) {
+        Ok(randomness) if randomness.fulfilled().is_some() => Some(randomness),
+        Ok(_) | Err(anchor_client::ClientError::AccountNotFound) => None,
+        Err(err) => return Err(err),
+    };
+
+    Ok(FulfillmentSubscription {
+        env,
+        fulfilled,
+        receiver,
+        _subscription: subscription,
+    })
+}
+
+/// Derives the websocket URL from the RPC URL.
+///
+/// Follows the Solana CLI convention: `http` becomes `ws` (`https` becomes `wss`),
+/// and an explicit port is incremented by one (e.g. `8899` becomes `8900`).
+pub fn websocket_url(rpc_url: &str) -> String {
+    let (scheme, rest) = match rpc_url.split_once("://") {
+        Some(("https", rest)) => ("wss", rest),
+        Some((_, rest)) => ("ws", rest),
+        None => ("ws", rpc_url),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    // the port of an IPv6 host (e.g. `[::1]`) never parses, so it is left as is
+    let authority = match authority
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+    {
+        Some((host, port)) => format!("{}:{}", host, port.saturating_add(1)),
+        None => authority.to_string(),
+    };
+    format!("{}://{}{}", scheme, authority, path)
+}
+
+fn subscription_closed() -> anchor_client::ClientError {
+    ClientError::from(ClientErrorKind::Custom(
+        "Fulfillment subscription is closed".to_string(),
+    ))
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn websocket_url_follows_solana_cli() {
+        assert_eq!(
+            websocket_url("https://api.devnet.solana.com"),
+            "wss://api.devnet.solana.com"
+        );
+        assert_eq!(
+            websocket_url("http://localhost:8899"),
+            "ws://localhost:8900"
+        );
+        assert_eq!(
+            websocket_url("https://rpc.example.com:8443/api-key/v1"),
+            "wss://rpc.example.com:8444/api-key/v1"
+        );
+        assert_eq!(
+            websocket_url("https://rpc.example.com/api-key"),
+            "wss://rpc.example.com/api-key"
+        );
+    }
+
+    #[test]
+    fn websocket_url_of_ipv6_host() {
+        assert_eq!(websocket_url("http://[::1]:8899"), "ws://[::1]:8900");
+        assert_eq!(websocket_url("http://[::1]"), "ws://[::1]");
+        assert_eq!(websocket_url("http://[::1]/rpc"), "ws://[::1]/rpc");
+    }
+
+    #[test]
+    fn websocket_url_without_scheme() {
+        assert_eq!(websocket_url("localhost:8899"), "ws://localhost:8900");
+        assert_eq!(
+            websocket_url("api.devnet.solana.com"),
+            "ws://api.devnet.solana.com"
+        );
+    }
+}
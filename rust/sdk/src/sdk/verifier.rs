@@ -0,0 +1,28 @@
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_lang::prelude::Pubkey;
+
+/// Ed25519 signature verification backend of the offchain verification helpers.
+///
+/// [`DefaultSignatureVerifier`] is used unless a different backend is given, e.g. to plug in
+/// a faster or a hardware-accelerated Ed25519 implementation for high-volume verification.
+/// See [`crate::state::Randomness::verify_offchain_with`] and [`crate::SignedMessage::verify_with`].
+pub trait SignatureVerifier {
+    /// Returns `true` if `signature` is a valid signature of `message` by `pubkey`.
+    fn verify(&self, pubkey: &Pubkey, message: &[u8], signature: &[u8; 64]) -> bool;
+}
+
+/// Verifies signatures with the Solana SDK Ed25519 implementation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultSignatureVerifier;
+
+impl SignatureVerifier for DefaultSignatureVerifier {
+    fn verify(&self, pubkey: &Pubkey, message: &[u8], signature: &[u8; 64]) -> bool {
+        Signature::from(*signature).verify(pubkey.as_ref(), message)
+    }
+}
+
+impl<T: SignatureVerifier + ?Sized> SignatureVerifier for &T {
+    fn verify(&self, pubkey: &Pubkey, message: &[u8], signature: &[u8; 64]) -> bool {
+        (**self).verify(pubkey, message, signature)
+    }
+}
@@ -0,0 +1,726 @@
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_client::RpcClient,
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        ed25519_instruction::{SIGNATURE_OFFSETS_SERIALIZED_SIZE, SIGNATURE_OFFSETS_START},
+        ed25519_program,
+        signature::Signature,
+        signer::Signer,
+        transaction::VersionedTransaction,
+    },
+};
+use anchor_lang::{prelude::Pubkey, InstructionData};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiTransactionEncoding,
+};
+
+use crate::{
+    randomness_account_address_for,
+    state::{Randomness, RandomnessResponse},
+    xor_array, SdkError, MAX_FULFILLMENT_AUTHORITIES_COUNT,
+};
+
+use std::ops::Deref;
+
+use super::{
+    get_network_state, get_randomness, history::successful_signatures, DefaultSignatureVerifier,
+    SignatureVerifier,
+};
+
+/// Defines which transactions of a randomness account are examined by [`verify_fulfillment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FulfillmentScan {
+    /// Examine at most this number of the most recent transactions.
+    Latest(usize),
+    /// Examine the whole transaction history of the account.
+    Exhaustive,
+}
+
+impl FulfillmentScan {
+    /// Returns the maximum number of transactions to examine (`None` for all of them).
+    pub(super) fn limit(self) -> Option<usize> {
+        match self {
+            Self::Latest(limit) => Some(limit),
+            Self::Exhaustive => None,
+        }
+    }
+}
+
+impl Default for FulfillmentScan {
+    /// Enough to cover the request transaction and a fulfill transaction
+    /// of every fulfillment authority.
+    fn default() -> Self {
+        Self::Latest(MAX_FULFILLMENT_AUTHORITIES_COUNT + 1)
+    }
+}
+
+/// Verifies randomness against its fulfill transactions.
+///
+/// Fetches successful transactions that touched the randomness account (newest first,
+/// as defined by the `scan`) and delegates to [`verify_fulfillment_with`].
+///
+/// Returns signed messages of the fulfillment authorities that signed the randomness.
+/// Fails with [`crate::SdkError::SeedMismatch`] if the randomness account is not
+/// the one of the requested `seed`, or with [`crate::SdkError::RandomnessNotFulfilled`]
+/// if there is nothing to verify yet.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let signed = orao_solana_vrf::verify_fulfillment(&program, &seed, Default::default())?;
+/// for message in signed {
+///     println!("Randomness signed by {}", message.pubkey);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn verify_fulfillment<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    scan: FulfillmentScan,
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+    let randomness = get_randomness(orao_vrf, seed)?;
+    randomness.check_seed(seed)?;
+    verify_fulfillment_of(orao_vrf, &randomness, scan)
+}
+
+/// Verifies randomness against the given fulfill transactions, skipping the scan
+/// of the randomness account history (see [`verify_fulfillment`]).
+///
+/// Only the given `signatures` are fetched, e.g. ones taken from an indexer that
+/// maintains its own seed → signatures map. Signatures of unrelated transactions are ignored.
+pub fn verify_with_signatures<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    signatures: &[Signature],
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+    let randomness = get_randomness(orao_vrf, seed)?;
+    randomness.check_seed(seed)?;
+    let rpc = orao_vrf.rpc();
+    verify_fulfillment_with(
+        &orao_vrf.id(),
+        &randomness,
+        signatures.iter().copied(),
+        |signature| fetch_transaction(&rpc, signature),
+    )
+}
+
+fn verify_fulfillment_of<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    randomness: &Randomness,
+    scan: FulfillmentScan,
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+    find_fulfillment_of(orao_vrf, randomness, scan)?.ok_or_else(verification_failed)
+}
+
+/// Same as [`verify_fulfillment_of`], but returns `None` if no fulfill transaction
+/// of the request is found.
+fn find_fulfillment_of<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    randomness: &Randomness,
+    scan: FulfillmentScan,
+) -> Result<Option<Vec<SignedMessage>>, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    let signatures = randomness_signatures(&rpc, &orao_vrf.id(), &randomness.seed, scan)?;
+    let transactions = signatures.iter().map(|signature| {
+        fetch_transaction(&rpc, signature)?
+            .decode()
+            .ok_or_else(|| not_binary(signature))
+    });
+    verify_transactions(&orao_vrf.id(), randomness, transactions)
+}
+
+/// Confirms that a successful `fulfill` transaction of the request exists and contains
+/// an `Ed25519SigVerify` instruction.
+///
+/// Unlike [`verify_fulfillment`] this doesn't check the signatures offchain, but relies
+/// on the on-chain verification instead.
+pub fn confirm_onchain_verification<C: Deref<Target = impl Signer> + Clone>(
+    orao_vrf: &anchor_client::Program<C>,
+    seed: &[u8; 32],
+    scan: FulfillmentScan,
+) -> Result<bool, anchor_client::ClientError> {
+    let rpc = orao_vrf.rpc();
+    for signature in randomness_signatures(&rpc, &orao_vrf.id(), seed, scan)? {
+        let transaction = fetch_transaction(&rpc, &signature)?
+            .decode()
+            .ok_or_else(|| not_binary(&signature))?;
+        if is_fulfill_transaction(&orao_vrf.id(), &transaction, seed)
+            && ed25519_instructions(&transaction).next().is_some()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns successful transactions that touched the randomness account (newest first).
+pub(super) fn randomness_signatures(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    seed: &[u8; 32],
+    scan: FulfillmentScan,
+) -> Result<Vec<Signature>, ClientError> {
+    let address = randomness_account_address_for(program_id, seed);
+    Ok(successful_signatures(rpc, &address, scan.limit())?
+        .into_iter()
+        .map(|x| x.signature)
+        .collect())
+}
+
+fn fetch_transaction(
+    rpc: &RpcClient,
+    signature: &Signature,
+) -> Result<EncodedTransaction, anchor_client::ClientError> {
+    let transaction = rpc.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    Ok(transaction.transaction.transaction)
+}
+
+/// Verifies randomness against its fulfill transactions using the given transaction source.
+///
+/// Returns signed messages of the fulfillment authorities that signed the randomness
+/// (in the order of the randomness account responses).
+///
+/// `fetch` is called for each of the `signatures` (in the given order) until every response
+/// is verified, and should return the transaction in a binary encoding (base58 or base64).
+/// Transactions other than `fulfill` transactions of this randomness request are ignored.
+///
+/// Verification succeeds if every response recorded in the randomness account is backed
+/// by a valid `Ed25519SigVerify` instruction over the seed recorded in the randomness
+/// account, and the responses combine into the fulfilled randomness. Fails with
+/// [`crate::Error::RandomnessVerificationFailed`] otherwise, or with
+/// [`crate::Error::InvalidFulfillMessage`] if a fulfill transaction carries a signature
+/// of anything but that seed.
+///
+/// Note that this does not check responders against the fulfillment authorities –
+/// use [`Randomness::verify_offchain`] for that.
+pub fn verify_fulfillment_with<F>(
+    program_id: &Pubkey,
+    randomness: &Randomness,
+    signatures: impl IntoIterator<Item = Signature>,
+    mut fetch: F,
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError>
+where
+    F: FnMut(&Signature) -> Result<EncodedTransaction, anchor_client::ClientError>,
+{
+    let transactions = signatures.into_iter().map(|signature| {
+        fetch(&signature)?
+            .decode()
+            .ok_or_else(|| not_binary(&signature))
+    });
+    verify_transactions(program_id, randomness, transactions)?.ok_or_else(verification_failed)
+}
+
+/// Verifies randomness against archived fulfill transactions without any RPC calls
+/// (see [`verify_fulfillment_with`]).
+///
+/// Takes transactions as returned by `getTransaction` in a binary encoding (base58 or base64),
+/// e.g. deserialized from the archived JSON with `serde_json`. Failed transactions are ignored.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+///
+/// # let randomness: orao_solana_vrf::state::Randomness = panic!();
+/// # let archived: Vec<String> = panic!();
+/// let transactions = archived
+///     .iter()
+///     .map(|json| serde_json::from_str::<EncodedConfirmedTransactionWithStatusMeta>(json))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// orao_solana_vrf::verify_fulfillment_offline(&orao_solana_vrf::id(), &randomness, &transactions)?;
+/// # Ok(()) }
+/// ```
+pub fn verify_fulfillment_offline(
+    program_id: &Pubkey,
+    randomness: &Randomness,
+    transactions: &[EncodedConfirmedTransactionWithStatusMeta],
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+    let transactions = transactions
+        .iter()
+        .filter(|x| {
+            x.transaction
+                .meta
+                .as_ref()
+                .is_none_or(|meta| meta.err.is_none())
+        })
+        .map(|x| -> Result<_, anchor_client::ClientError> {
+            x.transaction.transaction.decode().ok_or_else(|| {
+                ClientError::from(ClientErrorKind::Custom(format!(
+                    "Transaction of slot {} is not in a binary encoding",
+                    x.slot
+                )))
+                .into()
+            })
+        });
+    verify_transactions(program_id, randomness, transactions)?.ok_or_else(verification_failed)
+}
+
+fn not_binary(signature: &Signature) -> anchor_client::ClientError {
+    ClientError::from(ClientErrorKind::Custom(format!(
+        "Transaction {} is not in a binary encoding",
+        signature
+    )))
+    .into()
+}
+
+/// Verifies randomness against the given transactions, see [`verify_fulfillment_with`].
+///
+/// Returns `None` if none of the transactions is a fulfill transaction of the request.
+fn verify_transactions(
+    program_id: &Pubkey,
+    randomness: &Randomness,
+    transactions: impl Iterator<Item = Result<VersionedTransaction, anchor_client::ClientError>>,
+) -> Result<Option<Vec<SignedMessage>>, anchor_client::ClientError> {
+    randomness.check_fulfilled()?;
+    let find = |verified: &[SignedMessage], response: &RandomnessResponse| {
+        verified
+            .iter()
+            .find(|x| x.pubkey == response.pubkey && x.signature == response.randomness)
+            .cloned()
+    };
+    let is_verified = |verified: &[SignedMessage]| {
+        !randomness.responses.is_empty()
+            && randomness
+                .responses
+                .iter()
+                .all(|response| find(verified, response).is_some())
+    };
+
+    let mut found = false;
+    let mut verified = Vec::new();
+    for transaction in transactions {
+        if is_verified(&verified) {
+            break;
+        }
+        let transaction = transaction?;
+        found |= is_fulfill_transaction(program_id, &transaction, &randomness.seed);
+        verified.extend(fulfill_responses(
+            program_id,
+            &transaction,
+            &randomness.seed,
+        )?);
+    }
+    if !found {
+        return Ok(None);
+    }
+
+    let mut expected_randomness = [0_u8; 64];
+    let mut messages = Vec::with_capacity(randomness.responses.len());
+    for response in randomness.responses.iter() {
+        let message = find(&verified, response).ok_or_else(verification_failed)?;
+        xor_array(&mut expected_randomness, &response.randomness);
+        messages.push(message);
+    }
+
+    if randomness.fulfilled().is_some() && expected_randomness == randomness.randomness {
+        Ok(Some(messages))
+    } else {
+        Err(verification_failed())
+    }
+}
+
+/// Fulfillment authorities loaded once and reused across verifications.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seeds: Vec<[u8; 32]> = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let context = orao_solana_vrf::VerificationContext::load(&program)?;
+/// for seed in seeds {
+///     context.verify_fulfillment(&program, &seed, Default::default())?;
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationContext {
+    fulfillment_authorities: Vec<Pubkey>,
+}
+
+impl VerificationContext {
+    /// Creates a context for the given fulfillment authorities.
+    pub fn new(fulfillment_authorities: Vec<Pubkey>) -> Self {
+        Self {
+            fulfillment_authorities,
+        }
+    }
+
+    /// Loads the effective fulfillment authorities from the VRF on-chain state.
+    pub fn load<C: Deref<Target = impl Signer> + Clone>(
+        orao_vrf: &anchor_client::Program<C>,
+    ) -> Result<Self, anchor_client::ClientError> {
+        let network_state = get_network_state(orao_vrf)?;
+        Ok(Self::new(network_state.config.fulfillment_authorities))
+    }
+
+    /// Returns the fulfillment authorities.
+    pub fn fulfillment_authorities(&self) -> &[Pubkey] {
+        &self.fulfillment_authorities
+    }
+
+    /// Performs offchain verification against the fulfillment authorities
+    /// (see [`Randomness::verify_offchain`]).
+    pub fn verify_offchain(&self, randomness: &Randomness) -> bool {
+        randomness.verify_offchain(&self.fulfillment_authorities)
+    }
+
+    /// Performs offchain verification and asserts that the `expected_authority`
+    /// is one of the responders (see [`VerificationContext::verify_offchain`]).
+    ///
+    /// Fails with [`crate::SdkError::RandomnessNotFulfilled`] if the randomness
+    /// is not yet fulfilled, with [`crate::Error::RandomnessVerificationFailed`] if it
+    /// doesn't verify, or with [`crate::SdkError::UnexpectedAuthority`] if it is not
+    /// signed by the `expected_authority`.
+    pub fn verify_offchain_with_authority(
+        &self,
+        randomness: &Randomness,
+        expected_authority: &Pubkey,
+    ) -> Result<(), anchor_client::ClientError> {
+        randomness.check_fulfilled()?;
+        if !self.verify_offchain(randomness) {
+            return Err(verification_failed());
+        }
+        if !randomness
+            .responses
+            .iter()
+            .any(|response| response.pubkey == *expected_authority)
+        {
+            return Err(SdkError::UnexpectedAuthority.into());
+        }
+        Ok(())
+    }
+
+    /// Verifies signatures stored in the randomness account against the seed
+    /// and the fulfillment authorities (see [`Randomness::verify_offchain`]).
+    ///
+    /// Returns signed messages built from the randomness account responses.
+    pub fn verify_stored(
+        &self,
+        randomness: &Randomness,
+    ) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+        randomness.check_fulfilled()?;
+        if !self.verify_offchain(randomness) {
+            return Err(verification_failed());
+        }
+        Ok(randomness
+            .responses
+            .iter()
+            .map(|response| SignedMessage {
+                pubkey: response.pubkey,
+                signature: response.randomness,
+                message: randomness.seed.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Verifies randomness against the fulfillment authorities and its fulfill transactions
+    /// (see [`Randomness::verify_offchain`] and [`verify_fulfillment`]).
+    ///
+    /// Falls back to [`VerificationContext::verify_stored`] if no fulfill transaction
+    /// of the request is found (i.e. the signatures were only stored in the account).
+    /// Fulfill transactions that don't match the stored responses, or carry a signature
+    /// of anything but the seed, still fail the verification.
+    ///
+    /// Returns signed messages of the fulfillment authorities that signed the randomness.
+    pub fn verify_fulfillment<C: Deref<Target = impl Signer> + Clone>(
+        &self,
+        orao_vrf: &anchor_client::Program<C>,
+        seed: &[u8; 32],
+        scan: FulfillmentScan,
+    ) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+        let randomness = get_randomness(orao_vrf, seed)?;
+        randomness.check_seed(seed)?;
+        randomness.check_fulfilled()?;
+        if !self.verify_offchain(&randomness) {
+            return Err(verification_failed());
+        }
+        match find_fulfillment_of(orao_vrf, &randomness, scan)? {
+            Some(messages) => Ok(messages),
+            None => self.verify_stored(&randomness),
+        }
+    }
+}
+
+fn verification_failed() -> anchor_client::ClientError {
+    crate::Error::RandomnessVerificationFailed.into()
+}
+
+/// Verifies a single fulfillment authority response offline, without any RPC calls.
+///
+/// The response is an Ed25519 `signature` of the `seed` by the fulfillment `authority`
+/// (see [`crate::state::RandomnessResponse`]). Fails with
+/// [`crate::Error::RandomnessVerificationFailed`] if the signature is invalid.
+pub fn verify_signature(
+    seed: &[u8; 32],
+    signature: &[u8; 64],
+    authority: &Pubkey,
+) -> Result<(), anchor_client::ClientError> {
+    let signed = SignedMessage {
+        pubkey: *authority,
+        signature: *signature,
+        message: seed.to_vec(),
+    };
+    if !signed.verify() {
+        return Err(verification_failed());
+    }
+    Ok(())
+}
+
+/// Message signed by an `Ed25519SigVerify` instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedMessage {
+    /// Signer public key.
+    pub pubkey: Pubkey,
+    /// Ed25519 signature.
+    pub signature: [u8; 64],
+    /// Signed message bytes.
+    pub message: Vec<u8>,
+}
+
+impl SignedMessage {
+    /// Performs an offchain Ed25519 verification of the signature.
+    pub fn verify(&self) -> bool {
+        self.verify_with(DefaultSignatureVerifier)
+    }
+
+    /// Performs an offchain Ed25519 verification of the signature
+    /// using the given signature verification backend.
+    pub fn verify_with(&self, verifier: impl SignatureVerifier) -> bool {
+        verifier.verify(&self.pubkey, &self.message, &self.signature)
+    }
+}
+
+/// Returns valid responses found in the given `fulfill` transaction.
+///
+/// Returns nothing if this is not a `fulfill` transaction of the request for the `seed`.
+pub(super) fn fulfill_responses(
+    program_id: &Pubkey,
+    transaction: &VersionedTransaction,
+    seed: &[u8; 32],
+) -> Result<Vec<SignedMessage>, anchor_client::ClientError> {
+    if !is_fulfill_transaction(program_id, transaction, seed) {
+        return Ok(vec![]);
+    }
+
+    let signed = ed25519_instructions(transaction)
+        .flat_map(parse_ed25519_instruction)
+        .collect::<Vec<_>>();
+    if signed.iter().any(|x| x.message != seed) {
+        return Err(crate::Error::InvalidFulfillMessage.into());
+    }
+
+    Ok(signed.into_iter().filter(SignedMessage::verify).collect())
+}
+
+/// Checks whether this is a `fulfill` transaction of the request for the `seed`.
+fn is_fulfill_transaction(
+    program_id: &Pubkey,
+    transaction: &VersionedTransaction,
+    seed: &[u8; 32],
+) -> bool {
+    let keys = transaction.message.static_account_keys();
+    let fulfill_data = crate::instruction::Fulfill.data();
+    let request_address = randomness_account_address_for(program_id, seed);
+
+    transaction.message.instructions().iter().any(|ix| {
+        keys.get(ix.program_id_index as usize) == Some(program_id)
+            && ix.data == fulfill_data
+            // see `crate::Fulfill` for the accounts order
+            && ix.accounts.get(3).and_then(|i| keys.get(*i as usize)) == Some(&request_address)
+    })
+}
+
+/// Returns data of the `Ed25519SigVerify` instructions of the transaction.
+fn ed25519_instructions(transaction: &VersionedTransaction) -> impl Iterator<Item = &[u8]> {
+    let keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| keys.get(ix.program_id_index as usize) == Some(&ed25519_program::ID))
+        .map(|ix| ix.data.as_slice())
+}
+
+/// Parses `Ed25519SigVerify` instruction data.
+///
+/// Only signatures that keep all the data within the instruction itself are returned.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Vec<SignedMessage> {
+    let count = data.first().copied().unwrap_or_default() as usize;
+    (0..count)
+        .filter_map(|i| {
+            let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+            let offsets = data.get(start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE)?;
+            let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+            // signature, public key and message instruction indices
+            if [read(2), read(6), read(12)] != [u16::MAX; 3] {
+                return None;
+            }
+
+            let (signature, pubkey, message) =
+                (read(0) as usize, read(4) as usize, read(8) as usize);
+            let signature = data.get(signature..signature + 64)?.try_into().ok()?;
+            let pubkey = Pubkey::try_from(data.get(pubkey..pubkey + 32)?).ok()?;
+            let message = data.get(message..message + read(10) as usize)?.to_vec();
+
+            Some(SignedMessage {
+                pubkey,
+                signature,
+                message,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_sdk::{
+        ed25519_instruction::new_ed25519_instruction,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        system_instruction,
+        transaction::Transaction,
+    };
+
+    use super::*;
+
+    fn randomness() -> Randomness {
+        Randomness {
+            seed: [1; 32],
+            randomness: [3; 64],
+            responses: vec![RandomnessResponse {
+                pubkey: Pubkey::new_from_array([4; 32]),
+                randomness: [3; 64],
+            }],
+        }
+    }
+
+    fn keypair(byte: u8) -> ed25519_dalek::Keypair {
+        let secret = ed25519_dalek::SecretKey::from_bytes(&[byte; 32]).unwrap();
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        ed25519_dalek::Keypair { secret, public }
+    }
+
+    fn sign(keypair: &ed25519_dalek::Keypair, seed: &[u8; 32]) -> [u8; 64] {
+        ed25519_dalek::Signer::sign(keypair, seed).to_bytes()
+    }
+
+    fn transaction(instruction: Instruction) -> VersionedTransaction {
+        let payer = Pubkey::new_from_array([5; 32]);
+        Transaction::new_unsigned(Message::new(&[instruction], Some(&payer))).into()
+    }
+
+    fn fulfill_transaction(seed: &[u8; 32]) -> VersionedTransaction {
+        let mut accounts = (0..3)
+            .map(|i| AccountMeta::new_readonly(Pubkey::new_from_array([10 + i; 32]), false))
+            .collect::<Vec<_>>();
+        accounts.push(AccountMeta::new(
+            randomness_account_address_for(&crate::id(), seed),
+            false,
+        ));
+        transaction(Instruction::new_with_bytes(
+            crate::id(),
+            &crate::instruction::Fulfill.data(),
+            accounts,
+        ))
+    }
+
+    #[test]
+    fn reports_missing_fulfill_transaction() {
+        let unrelated = transaction(system_instruction::transfer(
+            &Pubkey::new_from_array([5; 32]),
+            &Pubkey::new_from_array([6; 32]),
+            1,
+        ));
+        let other_seed = fulfill_transaction(&[2; 32]);
+
+        let transactions = vec![Ok(unrelated), Ok(other_seed)];
+        let result = verify_transactions(&crate::id(), &randomness(), transactions.into_iter());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn fails_on_fulfill_transaction_mismatch() {
+        let transactions = vec![Ok(fulfill_transaction(&randomness().seed))];
+        let err =
+            verify_transactions(&crate::id(), &randomness(), transactions.into_iter()).unwrap_err();
+        assert!(crate::Error::RandomnessVerificationFailed.matches(&err));
+    }
+
+    #[test]
+    fn verify_signature_accepts_valid_response() {
+        let authority = keypair(7);
+        let seed = [1; 32];
+        let signature = sign(&authority, &seed);
+        let pubkey = Pubkey::new_from_array(authority.public.to_bytes());
+
+        assert!(verify_signature(&seed, &signature, &pubkey).is_ok());
+    }
+
+    #[test]
+    fn verify_signature_rejects_invalid_response() {
+        let authority = keypair(7);
+        let seed = [1; 32];
+        let signature = sign(&authority, &seed);
+        let pubkey = Pubkey::new_from_array(authority.public.to_bytes());
+        let failed = |result: Result<(), anchor_client::ClientError>| {
+            crate::Error::RandomnessVerificationFailed.matches(&result.unwrap_err())
+        };
+
+        let other = Pubkey::new_from_array(keypair(8).public.to_bytes());
+        assert!(failed(verify_signature(&seed, &signature, &other)));
+
+        let mut flipped = signature;
+        flipped[0] ^= 1;
+        assert!(failed(verify_signature(&seed, &flipped, &pubkey)));
+
+        assert!(failed(verify_signature(&[2; 32], &signature, &pubkey)));
+    }
+
+    #[test]
+    fn parses_ed25519_instruction() {
+        let authority = keypair(7);
+        let seed = [1; 32];
+        let instruction = new_ed25519_instruction(&authority, &seed);
+
+        let messages = parse_ed25519_instruction(&instruction.data);
+        assert_eq!(
+            messages,
+            vec![SignedMessage {
+                pubkey: Pubkey::new_from_array(authority.public.to_bytes()),
+                signature: sign(&authority, &seed),
+                message: seed.to_vec(),
+            }]
+        );
+        assert!(messages[0].verify());
+    }
+
+    #[test]
+    fn truncated_ed25519_instruction_is_rejected() {
+        let instruction = new_ed25519_instruction(&keypair(7), &[1; 32]);
+        for len in 0..instruction.data.len() {
+            assert!(parse_ed25519_instruction(&instruction.data[..len]).is_empty());
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use anchor_client::{
+    solana_client::client_error::{ClientError, ClientErrorKind},
+    solana_sdk::signer::Signer,
+};
+
+use crate::{
+    state::{Randomness, RandomnessStatus},
+    SdkError,
+};
+
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+use super::get_randomness;
+
+/// Waits for randomness being fulfilled.
+///
+/// Polls the randomness account with an exponential backoff: the poll interval doubles
+/// after every poll up to the maximum interval, so that quick fulfillments are detected
+/// fast while long waits stay cheap. A randomized delay is added to every interval,
+/// so that many clients waiting on the same oracle don't hit the RPC in lockstep.
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use anchor_client::*;
+/// use std::time::Duration;
+///
+/// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+/// # let seed: [u8; 32] = panic!();
+/// let client = Client::new(Cluster::Devnet, payer);
+/// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+///
+/// let randomness = orao_solana_vrf::FulfillmentWaiter::new(seed)
+///     .with_interval(Duration::from_secs(1))
+///     .with_max_interval(Duration::from_secs(10))
+///     .wait(&program)?;
+///
+/// println!("Randomness fulfilled: {:?}", randomness.fulfilled());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FulfillmentWaiter {
+    seed: [u8; 32],
+    interval: Duration,
+    max_interval: Duration,
+    jitter: Duration,
+    timeout: Option<Duration>,
+}
+
+impl FulfillmentWaiter {
+    /// Creates a new waiter for the given seed.
+    ///
+    /// Starts polling every 500ms backing off up to 5s, with up to 250ms of jitter
+    /// and no timeout by default.
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(5),
+            jitter: Duration::from_millis(250),
+            timeout: None,
+        }
+    }
+
+    /// Change the initial poll interval.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Change the maximum poll interval of the backoff.
+    ///
+    /// Set it to the initial interval (or below) to poll at a fixed interval.
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Change the maximum random delay added to the poll interval.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Give up waiting after the given time.
+    ///
+    /// Waits then fail with [`SdkError::Timeout`] if the randomness is not fulfilled
+    /// in time, whether the request account exists or not.
+    /// Doesn't affect [`FulfillmentWaiter::transitions`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Blocks until the randomness is fulfilled.
+    ///
+    /// Keeps polling if the randomness account doesn't exist yet.
+    pub fn wait<C: Deref<Target = impl Signer> + Clone>(
+        &self,
+        orao_vrf: &anchor_client::Program<C>,
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let mut attempt = 0;
+        loop {
+            match get_randomness(orao_vrf, &self.seed) {
+                Ok(randomness) if randomness.fulfilled().is_some() => return Ok(randomness),
+                Ok(_) | Err(anchor_client::ClientError::AccountNotFound) => (),
+                Err(err) => return Err(err),
+            }
+            std::thread::sleep(self.delay_until(attempt, deadline)?);
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Blocks until the randomness is fulfilled by any of the given VRF deployments.
+    ///
+    /// Returns the index of the deployment along with the randomness. Errors of a single
+    /// deployment are ignored as long as some other deployment is reachable.
+    /// Fails right away if no deployments are given.
+    pub fn wait_any<C: Deref<Target = impl Signer> + Clone>(
+        &self,
+        deployments: &[&anchor_client::Program<C>],
+    ) -> Result<(usize, Randomness), anchor_client::ClientError> {
+        if deployments.is_empty() {
+            return Err(ClientError::from(ClientErrorKind::Custom(
+                "No VRF deployments given".to_string(),
+            ))
+            .into());
+        }
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let mut attempt = 0;
+        loop {
+            let mut errors = Vec::new();
+            for (i, orao_vrf) in deployments.iter().enumerate() {
+                match get_randomness(orao_vrf, &self.seed) {
+                    Ok(randomness) if randomness.fulfilled().is_some() => {
+                        return Ok((i, randomness))
+                    }
+                    Ok(_) | Err(anchor_client::ClientError::AccountNotFound) => (),
+                    Err(err) => errors.push(err),
+                }
+            }
+            if errors.len() == deployments.len() {
+                if let Some(err) = errors.pop() {
+                    return Err(err);
+                }
+            }
+            std::thread::sleep(self.delay_until(attempt, deadline)?);
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Waits until the randomness is fulfilled (see [`FulfillmentWaiter::wait`]).
+    ///
+    /// Dropping the returned future stops the waiting. See also
+    /// [`FulfillmentWaiter::wait_async_until`].
+    ///
+    /// **Note:** requires `async` feature to be enabled.
+    #[cfg(feature = "async")]
+    pub async fn wait_async(
+        &self,
+        client: &super::nonblocking::VrfClient,
+    ) -> Result<Randomness, anchor_client::ClientError> {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let mut attempt = 0;
+        loop {
+            match client.get_randomness(&self.seed).await {
+                Ok(randomness) if randomness.fulfilled().is_some() => return Ok(randomness),
+                Ok(_) | Err(anchor_client::ClientError::AccountNotFound) => (),
+                Err(err) => return Err(err),
+            }
+            tokio::time::sleep(self.delay_until(attempt, deadline)?).await;
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Waits until the randomness is fulfilled or the `cancel` future completes.
+    ///
+    /// Returns `None` if cancelled. Any future could serve as a cancellation signal,
+    /// e.g. a `tokio_util::sync::CancellationToken::cancelled()` or a oneshot receiver.
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use orao_solana_vrf::{nonblocking::VrfClient, FulfillmentWaiter};
+    ///
+    /// # let client: VrfClient = panic!();
+    /// # let seed: [u8; 32] = panic!();
+    /// let (cancel, cancelled) = futures::channel::oneshot::channel::<()>();
+    /// # drop(cancel);
+    /// match FulfillmentWaiter::new(seed).wait_async_until(&client, cancelled).await? {
+    ///     Some(randomness) => println!("Randomness fulfilled: {:?}", randomness.fulfilled()),
+    ///     None => println!("Cancelled"),
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// **Note:** requires `async` feature to be enabled.
+    #[cfg(feature = "async")]
+    pub async fn wait_async_until(
+        &self,
+        client: &super::nonblocking::VrfClient,
+        cancel: impl std::future::Future,
+    ) -> Result<Option<Randomness>, anchor_client::ClientError> {
+        let wait = std::pin::pin!(self.wait_async(client));
+        let cancel = std::pin::pin!(cancel);
+        match futures::future::select(wait, cancel).await {
+            futures::future::Either::Left((result, _)) => result.map(Some),
+            futures::future::Either::Right(_) => Ok(None),
+        }
+    }
+
+    /// Returns an iterator over the request status transitions.
+    ///
+    /// Yields the current status first, then blocks until the status changes
+    /// and yields every change once, e.g. [`RandomnessStatus::NotRequested`] →
+    /// [`RandomnessStatus::Pending`] → [`RandomnessStatus::Fulfilled`].
+    /// Ends after [`RandomnessStatus::Fulfilled`] or after the first error.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use anchor_client::*;
+    ///
+    /// # let payer: std::rc::Rc<solana_sdk::signer::keypair::Keypair> = panic!();
+    /// # let seed: [u8; 32] = panic!();
+    /// let client = Client::new(Cluster::Devnet, payer);
+    /// let program = client.program(orao_solana_vrf::id()).expect("unable to get a program");
+    ///
+    /// for status in orao_solana_vrf::FulfillmentWaiter::new(seed).transitions(&program) {
+    ///     println!("Randomness status: {:?}", status?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn transitions<'a, C: Deref<Target = impl Signer> + Clone>(
+        &'a self,
+        orao_vrf: &'a anchor_client::Program<C>,
+    ) -> impl Iterator<Item = Result<RandomnessStatus, anchor_client::ClientError>> + 'a {
+        let mut last = None;
+        let mut done = false;
+        let mut attempt = 0;
+        std::iter::from_fn(move || loop {
+            if done {
+                return None;
+            }
+            let status = match get_randomness(orao_vrf, &self.seed) {
+                Ok(randomness) => randomness.status(),
+                Err(anchor_client::ClientError::AccountNotFound) => RandomnessStatus::NotRequested,
+                Err(err) => {
+                    done = true;
+                    return Some(Err(err));
+                }
+            };
+            if last != Some(status) {
+                last = Some(status);
+                done = status == RandomnessStatus::Fulfilled;
+                // back off anew after every change
+                attempt = 0;
+                return Some(Ok(status));
+            }
+            std::thread::sleep(self.delay(attempt));
+            attempt = attempt.saturating_add(1);
+        })
+    }
+
+    /// Returns the delay before the next poll, capped by the `deadline`.
+    ///
+    /// Fails with [`SdkError::Timeout`] once the deadline has passed.
+    fn delay_until(
+        &self,
+        attempt: u32,
+        deadline: Option<Instant>,
+    ) -> Result<Duration, anchor_client::ClientError> {
+        let delay = self.delay(attempt);
+        let Some(deadline) = deadline else {
+            return Ok(delay);
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(SdkError::Timeout.into());
+        }
+        Ok(delay.min(remaining))
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        let interval = self
+            .interval
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.max_interval.max(self.interval));
+        interval + self.jitter.mul_f64(rand::random::<f64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::{solana_sdk::signature::Keypair, Program};
+
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn wait_any_fails_without_deployments() {
+        let deployments: &[&Program<Rc<Keypair>>] = &[];
+        let err = FulfillmentWaiter::new([1; 32])
+            .wait_any(deployments)
+            .unwrap_err();
+        assert!(err.to_string().contains("No VRF deployments given"));
+    }
+}
@@ -14,10 +14,15 @@ pub struct OraoTokenFeeConfig {
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 #[cfg_attr(feature = "sdk", derive(Debug))]
 pub struct NetworkConfiguration {
+    /// Configuration authority.
     pub authority: Pubkey,
+    /// Treasury receiving the request fees paid in lamports.
     pub treasury: Pubkey,
+    /// Request fee in lamports.
     pub request_fee: u64,
+    /// Effective fulfillment authorities.
     pub fulfillment_authorities: Vec<Pubkey>,
+    /// Token fee configuration, if fees could be paid in SPL token.
     pub token_fee_config: Option<OraoTokenFeeConfig>,
 }
 
@@ -29,15 +34,132 @@ pub struct NetworkState {
     pub num_received: u64,
 }
 
+/// Randomness request state.
+///
+/// With `serde` feature enabled byte arrays are (de)serialized as base58 strings.
 #[account]
+#[derive(PartialEq, Eq)]
 #[cfg_attr(feature = "sdk", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Randomness {
+    #[cfg_attr(feature = "serde", serde(with = "base58"))]
     pub seed: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(with = "base58"))]
     pub randomness: [u8; 64],
     pub responses: Vec<RandomnessResponse>,
 }
 
+/// Randomness request status (see [`Randomness::status`]).
+///
+/// This is the complete lifecycle of a request: `NotRequested` → `Pending` → `Fulfilled`.
+/// There are no failed or expired states – a request transaction that fails creates
+/// no account (the seed stays `NotRequested`), a pending request waits for
+/// the fulfillment indefinitely, and randomness accounts are never closed.
+///
+/// The status is derived from the account data: the request is `Fulfilled` once the
+/// combined randomness is non-zero, i.e. once enough fulfillment authorities have
+/// responded, and `Pending` otherwise (even if some of the authorities have responded,
+/// see [`Randomness::responder_count`]).
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sdk", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RandomnessStatus {
+    /// There is no request for the seed (the randomness account doesn't exist).
+    ///
+    /// Never returned by [`Randomness::status`] – only by helpers that look up accounts.
+    NotRequested,
+    /// The request is waiting for fulfillment (the randomness is all zeros).
+    Pending,
+    /// The randomness is fulfilled and final (see [`Randomness::fulfilled`]).
+    Fulfilled,
+}
+
 impl Randomness {
+    /// Returns the request status.
+    pub fn status(&self) -> RandomnessStatus {
+        if self.fulfilled().is_some() {
+            RandomnessStatus::Fulfilled
+        } else {
+            RandomnessStatus::Pending
+        }
+    }
+
+    /// Returns the number of fulfillment authorities that have responded so far.
+    pub fn responder_count(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Selects an index with probability proportional to its weight.
+    ///
+    /// Returns `None` if randomness is not yet fulfilled, or if the weights sum up to zero.
+    ///
+    /// The algorithm (reproducible on-chain and in other languages):
+    ///
+    /// 1. `total` is the sum of the weights (as a 128-bit integer, so it can't overflow),
+    /// 2. `x` is the first 16 bytes of the randomness as a little-endian 128-bit integer,
+    /// 3. `r = x % total` – the modulo bias is below `total / 2^128`
+    ///    (i.e. below `2^-64` for weights summing up to less than `2^64`),
+    /// 4. the result is the first index whose cumulative weight (sum of the weights
+    ///    up to and including the index) is greater than `r`.
+    ///
+    /// Zero weights are never selected.
+    pub fn weighted_choice(&self, weights: &[u64]) -> Option<usize> {
+        let randomness = self.fulfilled()?;
+        let total = weights.iter().map(|x| *x as u128).sum::<u128>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut x = [0_u8; 16];
+        x.copy_from_slice(&randomness[..16]);
+        let r = u128::from_le_bytes(x) % total;
+
+        let mut cumulative = 0_u128;
+        weights.iter().position(|weight| {
+            cumulative += *weight as u128;
+            cumulative > r
+        })
+    }
+
+    /// Returns the first 8 bytes of the randomness as a little-endian `u64`.
+    ///
+    /// Returns `None` if randomness is not yet fulfilled.
+    pub fn as_u64(&self) -> Option<u64> {
+        let randomness = self.fulfilled()?;
+        let mut x = [0_u8; 8];
+        x.copy_from_slice(&randomness[..8]);
+        Some(u64::from_le_bytes(x))
+    }
+
+    /// Returns a uniform value in `[0, 1)`.
+    ///
+    /// The value is the upper 53 bits of [`Randomness::as_u64`] divided by `2^53`,
+    /// so every value is exactly representable and equally likely.
+    ///
+    /// Returns `None` if randomness is not yet fulfilled.
+    pub fn as_f64_unit(&self) -> Option<f64> {
+        Some((self.as_u64()? >> 11) as f64 / (1_u64 << 53) as f64)
+    }
+
+    /// Returns a value in the inclusive range `low..=high`.
+    ///
+    /// The value is `low + x % (high - low + 1)`, where `x` is the first 16 bytes
+    /// of the randomness as a little-endian 128-bit integer – the modulo bias is
+    /// below `2^-64` (see [`Randomness::weighted_choice`]).
+    ///
+    /// Returns `None` if randomness is not yet fulfilled, or if `low > high`.
+    pub fn in_range(&self, low: u64, high: u64) -> Option<u64> {
+        if low > high {
+            return None;
+        }
+        let randomness = self.fulfilled()?;
+        let span = (high - low) as u128 + 1;
+
+        let mut x = [0_u8; 16];
+        x.copy_from_slice(&randomness[..16]);
+        Some(low + (u128::from_le_bytes(x) % span) as u64)
+    }
+
     /// Returns fulfilled randomness.
     ///
     /// Returns `None` if randomness is not yet fulfilled.
@@ -50,9 +172,122 @@ impl Randomness {
     }
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "sdk", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RandomnessResponse {
+    #[cfg_attr(feature = "serde", serde(with = "base58"))]
     pub pubkey: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "base58"))]
     pub randomness: [u8; 64],
 }
+
+/// Serde helpers to (de)serialize byte arrays and public keys as base58 strings.
+#[cfg(feature = "serde")]
+mod base58 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        bytes: impl AsRef<[u8]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bs58::encode(bytes).into_string())
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: for<'a> TryFrom<&'a [u8]>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = bs58::decode(&encoded)
+            .into_vec()
+            .map_err(D::Error::custom)?;
+        T::try_from(bytes.as_slice())
+            .map_err(|_| D::Error::invalid_length(bytes.len(), &"a byte array"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn randomness(bytes: [u8; 64]) -> Randomness {
+        Randomness {
+            seed: [1; 32],
+            randomness: bytes,
+            responses: vec![],
+        }
+    }
+
+    /// Randomness bytes `1, 2, …, 64`.
+    fn fixed() -> Randomness {
+        let mut bytes = [0; 64];
+        for (i, x) in bytes.iter_mut().enumerate() {
+            *x = i as u8 + 1;
+        }
+        randomness(bytes)
+    }
+
+    #[test]
+    fn conversions_of_unfulfilled_randomness() {
+        let pending = randomness([0; 64]);
+        assert_eq!(pending.as_u64(), None);
+        assert_eq!(pending.as_f64_unit(), None);
+        assert_eq!(pending.in_range(0, 10), None);
+    }
+
+    #[test]
+    fn conversions_of_fixed_randomness() {
+        // little-endian, so that other clients could reproduce the values
+        assert_eq!(fixed().as_u64(), Some(0x0807_0605_0403_0201));
+        assert_eq!(
+            fixed().as_f64_unit(),
+            Some((0x0807_0605_0403_0201_u64 >> 11) as f64 / (1_u64 << 53) as f64)
+        );
+        // 0x100f0e0d0c0b0a090807060504030201 % 201 == 112
+        assert_eq!(fixed().in_range(100, 300), Some(212));
+        assert_eq!(fixed().in_range(0, 999), Some(561));
+    }
+
+    #[test]
+    fn in_range_bounds() {
+        assert_eq!(fixed().in_range(6, 5), None);
+        assert_eq!(fixed().in_range(5, 5), Some(5));
+        assert!(randomness([0xff; 64]).in_range(0, u64::MAX).is_some());
+        assert_eq!(
+            randomness([0xff; 64]).in_range(1, u64::MAX),
+            Some(1 + (u128::MAX % u64::MAX as u128) as u64)
+        );
+    }
+
+    #[test]
+    fn as_f64_unit_stays_below_one() {
+        let unit = randomness([0xff; 64]).as_f64_unit().unwrap();
+        assert!(unit < 1.0);
+        assert!(unit > 0.999);
+    }
+
+    #[test]
+    fn weighted_choice_without_weights() {
+        assert_eq!(fixed().weighted_choice(&[]), None);
+        assert_eq!(fixed().weighted_choice(&[0, 0, 0]), None);
+        assert_eq!(randomness([0; 64]).weighted_choice(&[1, 2]), None);
+    }
+
+    #[test]
+    fn weighted_choice_of_fixed_randomness() {
+        // 0x100f0e0d0c0b0a090807060504030201 % 10 == 1, cumulative weights are 1, 1, 5, 10
+        assert_eq!(fixed().weighted_choice(&[1, 0, 4, 5]), Some(2));
+        // weights sum up beyond u64 without overflowing
+        assert_eq!(fixed().weighted_choice(&[u64::MAX, u64::MAX]), Some(1));
+    }
+
+    #[test]
+    fn weighted_choice_skips_zero_weights() {
+        for i in 1..=255 {
+            let choice = randomness([i; 64]).weighted_choice(&[0, 3, 0, 2, 0]);
+            assert!(matches!(choice, Some(1 | 3)), "{:?}", choice);
+        }
+    }
+}